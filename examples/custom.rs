@@ -7,13 +7,15 @@
 //! - Using a custom icon
 //! - Using Rich Text
 //! - Using background colors for the menus
+//! - Additional key bindings
 
 use bevy::prelude::*;
 
 use bevy_quickmenu::{
+    input::MenuInputMap,
     style::{ControlState, StyleEntry, Stylesheet},
-    ActionTrait, Menu, MenuIcon, MenuItem, MenuOptions, MenuState, QuickMenuPlugin, RichTextEntry,
-    ScreenTrait,
+    ActionTrait, Menu, MenuIcon, MenuItem, MenuOptions, MenuState, NavigationEvent,
+    QuickMenuPlugin, RichTextEntry, ScreenTrait,
 };
 
 fn main() {
@@ -43,9 +45,16 @@ pub struct BasicPlugin;
 
 impl Plugin for BasicPlugin {
     fn build(&self, app: &mut App) {
-        // Load a custom font
+        // Load a custom font and additionally allow navigating with WASD, Space and Escape
         let options = MenuOptions {
             font: Some("font.otf"),
+            input_map: MenuInputMap::default()
+                .with_key(KeyCode::KeyW, NavigationEvent::Up)
+                .with_key(KeyCode::KeyS, NavigationEvent::Down)
+                .with_key(KeyCode::KeyA, NavigationEvent::Left)
+                .with_key(KeyCode::KeyD, NavigationEvent::Right)
+                .with_key(KeyCode::Space, NavigationEvent::Select)
+                .with_key(KeyCode::Escape, NavigationEvent::Back),
             ..Default::default()
         };
 
@@ -7,8 +7,9 @@
 use bevy::{prelude::*, utils::HashMap};
 
 use bevy_quickmenu::{
-    style::Stylesheet, ActionTrait, Menu, MenuIcon, MenuItem, MenuState, QuickMenuPlugin,
-    ScreenTrait,
+    input::BoundInput,
+    style::{DescriptionPanel, Stylesheet},
+    ActionTrait, Menu, MenuIcon, MenuItem, MenuState, QuickMenuPlugin, ScreenTrait,
 };
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, States, Default)]
@@ -57,11 +58,16 @@ mod settings {
     #[derive(Debug, Clone)]
     struct CustomState {
         sound_on: bool,
+        volume: f32,
         gamepads: Vec<(Gamepad, String)>,
         controls: HashMap<usize, ControlDevice>,
+        key_bindings: [Option<BoundInput>; 3],
         logo: Handle<Image>,
     }
 
+    /// The names of the `key_bindings`
+    const KEY_BINDINGS: [&str; 3] = ["Jump", "Fire", "Pause"];
+
     pub struct SettingsPlugin;
 
     impl Plugin for SettingsPlugin {
@@ -82,11 +88,14 @@ mod settings {
 
     fn setup_system(mut commands: Commands, assets: Res<AssetServer>) {
         // Create a default stylesheet. You can customize these as you wish
-        let sheet = Stylesheet::default().with_background(BackgroundColor(Color::BLACK));
+        let sheet = Stylesheet::default()
+            .with_background(BackgroundColor(Color::BLACK))
+            .with_description(DescriptionPanel::default());
 
         commands.insert_resource(MenuState::new(
             CustomState {
                 sound_on: true,
+                volume: 80.0,
                 gamepads: Vec::new(),
                 controls: [
                     (0, ControlDevice::keyboard1()),
@@ -95,6 +104,11 @@ mod settings {
                     (3, ControlDevice::keyboard4()),
                 ]
                 .into(),
+                key_bindings: [
+                    Some(BoundInput::Key(KeyCode::Space)),
+                    Some(BoundInput::Key(KeyCode::KeyF)),
+                    None,
+                ],
                 logo: assets.load("logo.png"),
             },
             Screens::Root,
@@ -131,7 +145,9 @@ mod settings {
         Close,
         SoundOn,
         SoundOff,
+        Volume,
         Control(usize, ControlDevice),
+        Bind(usize),
     }
 
     /// Handle the possible actions
@@ -149,9 +165,28 @@ mod settings {
                 Actions::SoundOff => {
                     state.sound_on = false;
                 }
+                Actions::Volume => {}
                 Actions::Control(p, d) => {
                     state.controls.insert(*p, *d);
                 }
+                Actions::Bind(_) => {}
+            }
+        }
+
+        fn set_binding(
+            &self,
+            input: BoundInput,
+            state: &mut CustomState,
+            _: &mut EventWriter<MyEvent>,
+        ) {
+            if let Actions::Bind(index) = self {
+                state.key_bindings[*index] = Some(input);
+            }
+        }
+
+        fn set_value(&self, value: f32, state: &mut CustomState, _: &mut EventWriter<MyEvent>) {
+            if let Actions::Volume = self {
+                state.volume = value;
             }
         }
     }
@@ -161,6 +196,7 @@ mod settings {
     enum Screens {
         Root,
         Controls,
+        KeyBindings,
         Sound,
         Player(usize),
     }
@@ -172,6 +208,7 @@ mod settings {
             match self {
                 Screens::Root => root_menu(state),
                 Screens::Controls => controls_menu(state),
+                Screens::KeyBindings => key_bindings_menu(state),
                 Screens::Sound => sound_menu(state),
                 Screens::Player(p) => player_controls_menu(state, *p),
             }
@@ -185,9 +222,16 @@ mod settings {
             vec![
                 MenuItem::image(state.logo.clone()),
                 MenuItem::headline("Menu"),
-                MenuItem::action("Start", Actions::Close),
-                MenuItem::screen("Sound", Screens::Sound).with_icon(MenuIcon::Sound),
-                MenuItem::screen("Controls", Screens::Controls).with_icon(MenuIcon::Controls),
+                MenuItem::action("Start", Actions::Close).with_description("Back to the game"),
+                MenuItem::screen("Sound", Screens::Sound)
+                    .with_icon(MenuIcon::Sound)
+                    .with_description("Sound effects and music"),
+                MenuItem::screen("Controls", Screens::Controls)
+                    .with_icon(MenuIcon::Controls)
+                    .with_description("Which device each player uses"),
+                MenuItem::screen("Key Bindings", Screens::KeyBindings)
+                    .with_icon(MenuIcon::Controls)
+                    .with_description("Remap the controls of the game"),
             ],
         )
     }
@@ -200,6 +244,8 @@ mod settings {
                 MenuItem::label("Toggles sound and music"),
                 MenuItem::action("On", Actions::SoundOn).checked(state.sound_on),
                 MenuItem::action("Off", Actions::SoundOff).checked(!state.sound_on),
+                MenuItem::slider("Volume", Actions::Volume, state.volume, 0.0..=100.0, 10.0)
+                    .with_description("Use left and right to change the volume"),
             ],
         )
     }
@@ -217,6 +263,21 @@ mod settings {
         )
     }
 
+    /// This is displayed if the user selects `Key Bindings` in the `root_menu`.
+    /// Selecting a binding waits for the next key, mouse or gamepad input.
+    fn key_bindings_menu(state: &CustomState) -> Menu<Screens> {
+        Menu::new(
+            "key_bindings",
+            KEY_BINDINGS
+                .iter()
+                .enumerate()
+                .map(|(index, name)| {
+                    MenuItem::key_binding(*name, Actions::Bind(index), state.key_bindings[index])
+                })
+                .collect(),
+        )
+    }
+
     /// This is displayed if the user selects a player in the `controls_menu`
     fn player_controls_menu(state: &CustomState, player: usize) -> Menu<Screens> {
         let selected_control = state.controls[&player];
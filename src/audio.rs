@@ -0,0 +1,57 @@
+//! Navigation sounds
+//! Available with the `audio` feature. The `MenuSounds` of the `MenuOptions`
+//! of the first plugin are played whenever the corresponding `MenuSound` is
+//! sent.
+
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+use crate::types::MenuSound;
+pub use crate::types::MenuSounds;
+
+#[derive(Resource)]
+pub(crate) struct MenuSoundAssets {
+    focus: Option<Handle<AudioSource>>,
+    select: Option<Handle<AudioSource>>,
+    back: Option<Handle<AudioSource>>,
+    error: Option<Handle<AudioSource>>,
+    value_change: Option<Handle<AudioSource>>,
+    volume: f32,
+}
+
+impl MenuSoundAssets {
+    pub(crate) fn new(sounds: &MenuSounds, world: &World) -> Self {
+        let assets = world.get_resource::<AssetServer>().unwrap();
+        let load = |path: Option<&'static str>| path.map(|path| assets.load(path));
+        Self {
+            focus: load(sounds.focus),
+            select: load(sounds.select),
+            back: load(sounds.back),
+            error: load(sounds.error),
+            value_change: load(sounds.value_change),
+            volume: sounds.volume.unwrap_or(1.0),
+        }
+    }
+}
+
+pub(crate) fn sound_system(
+    mut commands: Commands,
+    mut reader: EventReader<MenuSound>,
+    sounds: Res<MenuSoundAssets>,
+) {
+    for sound in reader.read() {
+        let source = match sound {
+            MenuSound::Focus => &sounds.focus,
+            MenuSound::Select => &sounds.select,
+            MenuSound::Back => &sounds.back,
+            MenuSound::Error => &sounds.error,
+            MenuSound::ValueChange => &sounds.value_change,
+        };
+        if let Some(source) = source {
+            commands.spawn(AudioBundle {
+                source: source.clone(),
+                settings: PlaybackSettings::DESPAWN.with_volume(Volume::new(sounds.volume)),
+            });
+        }
+    }
+}
@@ -0,0 +1,549 @@
+//! Declarative menu definitions
+//! Screens can be described in `.menu.ron` asset files instead of Rust code.
+//! Actions and screens are referenced by string ids, which are mapped onto
+//! the `ActionTrait` / `ScreenTrait` types via `MenuDefinitionAdapter`.
+//!
+//! ```ron
+//! (
+//!     screens: {
+//!         "root": (
+//!             items: [
+//!                 Headline(label: "Main Menu"),
+//!                 Screen(label: "Settings", screen: "settings", icon: Settings),
+//!                 Action(label: "Quit", action: "quit", icon: Back),
+//!             ],
+//!         ),
+//!         "settings": (
+//!             title: Some("Settings"),
+//!             items: [
+//!                 Slider(label: "Volume", action: "volume", min: 0.0, max: 1.0, step: 0.1),
+//!                 TextInput(label: "Name", action: "name", placeholder: "Player"),
+//!                 KeyBinding(label: "Jump", action: "bind_jump"),
+//!                 Image(path: "logo.png"),
+//!             ],
+//!         ),
+//!     },
+//! )
+//! ```
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+use crate::input::BoundInput;
+use crate::systems::{menu_exists, redraw_system, Menus};
+use crate::types::{
+    CharacterFilter, Confirmation, Menu, MenuIcon, MenuItem, MenuSelection, WidgetLabel,
+};
+use crate::ScreenTrait;
+
+/// All screens of a `.menu.ron` file, keyed by their screen id
+#[derive(Asset, TypePath, Debug, Clone, Default, Deserialize)]
+pub struct MenuDefinition {
+    pub screens: HashMap<String, ScreenDefinition>,
+    /// The images referenced by the items, keyed by their path
+    #[serde(skip)]
+    pub images: HashMap<String, Handle<Image>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ScreenDefinition {
+    /// The id of the resulting `Menu`. Defaults to the screen id
+    #[serde(default)]
+    pub id: Option<String>,
+    pub items: Vec<ItemDefinition>,
+    #[serde(default)]
+    pub visible_rows: Option<usize>,
+    /// Shown in the breadcrumb
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum ItemDefinition {
+    Screen {
+        label: String,
+        screen: String,
+        #[serde(default)]
+        icon: IconDefinition,
+        #[serde(default)]
+        disabled: bool,
+    },
+    Action {
+        label: String,
+        action: String,
+        #[serde(default)]
+        icon: IconDefinition,
+        #[serde(default)]
+        disabled: bool,
+        /// The question of a confirmation dialog, see `MenuItem::with_confirmation`
+        #[serde(default)]
+        confirmation: Option<String>,
+    },
+    /// The current value is read via [`MenuDefinitionAdapter::value`]
+    Slider {
+        label: String,
+        action: String,
+        min: f32,
+        max: f32,
+        step: f32,
+        #[serde(default)]
+        icon: IconDefinition,
+    },
+    /// The current text is read via [`MenuDefinitionAdapter::text`]
+    TextInput {
+        label: String,
+        action: String,
+        #[serde(default)]
+        placeholder: String,
+        #[serde(default)]
+        max_length: Option<usize>,
+        #[serde(default)]
+        filter: FilterDefinition,
+        #[serde(default)]
+        icon: IconDefinition,
+    },
+    /// The bound input is read via [`MenuDefinitionAdapter::binding`]
+    KeyBinding {
+        label: String,
+        action: String,
+        #[serde(default)]
+        icon: IconDefinition,
+    },
+    Label {
+        label: String,
+        #[serde(default)]
+        icon: IconDefinition,
+    },
+    Headline {
+        label: String,
+        #[serde(default)]
+        icon: IconDefinition,
+    },
+    Image {
+        path: String,
+    },
+}
+
+/// The `MenuIcon`s, with `Image` referencing an image asset by path
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub enum IconDefinition {
+    #[default]
+    None,
+    Checked,
+    Unchecked,
+    Back,
+    Controls,
+    Sound,
+    Players,
+    Settings,
+    Image(String),
+}
+
+/// The `CharacterFilter`s that can be described in a definition
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub enum FilterDefinition {
+    #[default]
+    Any,
+    Alphanumeric,
+    AlphanumericAndSpace,
+    Numeric,
+}
+
+impl From<&FilterDefinition> for CharacterFilter {
+    fn from(filter: &FilterDefinition) -> Self {
+        match filter {
+            FilterDefinition::Any => CharacterFilter::Any,
+            FilterDefinition::Alphanumeric => CharacterFilter::Alphanumeric,
+            FilterDefinition::AlphanumericAndSpace => CharacterFilter::AlphanumericAndSpace,
+            FilterDefinition::Numeric => CharacterFilter::Numeric,
+        }
+    }
+}
+
+impl MenuDefinition {
+    fn icon(&self, icon: &IconDefinition) -> MenuIcon {
+        match icon {
+            IconDefinition::None => MenuIcon::None,
+            IconDefinition::Checked => MenuIcon::Checked,
+            IconDefinition::Unchecked => MenuIcon::Unchecked,
+            IconDefinition::Back => MenuIcon::Back,
+            IconDefinition::Controls => MenuIcon::Controls,
+            IconDefinition::Sound => MenuIcon::Sound,
+            IconDefinition::Players => MenuIcon::Players,
+            IconDefinition::Settings => MenuIcon::Settings,
+            IconDefinition::Image(path) => self
+                .images
+                .get(path)
+                .map(|handle| MenuIcon::Other(handle.clone()))
+                .unwrap_or(MenuIcon::None),
+        }
+    }
+
+    fn image_paths(&self) -> impl Iterator<Item = &String> {
+        self.screens
+            .values()
+            .flat_map(|screen| screen.items.iter())
+            .filter_map(|item| match item {
+                ItemDefinition::Image { path } => Some(path),
+                ItemDefinition::Screen { icon, .. }
+                | ItemDefinition::Action { icon, .. }
+                | ItemDefinition::Slider { icon, .. }
+                | ItemDefinition::TextInput { icon, .. }
+                | ItemDefinition::KeyBinding { icon, .. }
+                | ItemDefinition::Label { icon, .. }
+                | ItemDefinition::Headline { icon, .. } => match icon {
+                    IconDefinition::Image(path) => Some(path),
+                    _ => None,
+                },
+            })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MenuDefinitionLoaderError {
+    #[error("Could not read the menu definition: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse the menu definition: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+/// Loads `MenuDefinition`s from `.menu.ron` files
+#[derive(Default)]
+pub struct MenuDefinitionLoader;
+
+impl AssetLoader for MenuDefinitionLoader {
+    type Asset = MenuDefinition;
+    type Settings = ();
+    type Error = MenuDefinitionLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut definition: MenuDefinition = ron::de::from_bytes(&bytes)?;
+        let paths: Vec<String> = definition.image_paths().cloned().collect();
+        for path in paths {
+            let handle = load_context.load(path.clone());
+            definition.images.insert(path, handle);
+        }
+        Ok(definition)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["menu.ron"]
+    }
+}
+
+/// Maps the string ids of a `MenuDefinition` onto the `ScreenTrait` and
+/// `ActionTrait` types. Implement `ScreenTrait::resolve` by calling
+/// `resolve_definition`:
+///
+/// ```ignore
+/// impl ScreenTrait for Screens {
+///     type Action = Actions;
+///     type State = MyState;
+///     fn resolve(&self, state: &MyState) -> Menu<Screens> {
+///         self.resolve_definition(state)
+///     }
+/// }
+/// ```
+pub trait MenuDefinitionAdapter: ScreenTrait {
+    /// The definition is kept in the state so that screens can be resolved
+    /// from it. It is updated by the `MenuDefinitionPlugin`.
+    fn definition(state: &Self::State) -> &MenuDefinition;
+    fn definition_mut(state: &mut Self::State) -> &mut MenuDefinition;
+
+    /// The id of this screen in the definition
+    fn screen_id(&self) -> &str;
+    fn screen_from_id(id: &str) -> Option<Self>;
+    fn action_from_id(id: &str) -> Option<Self::Action>;
+
+    /// If `Some`, the item for this action is rendered as checked or unchecked
+    fn checked(_action: &Self::Action, _state: &Self::State) -> Option<bool> {
+        None
+    }
+
+    /// Disable an item in addition to the `disabled` flag of the definition
+    fn disabled(_selection: &MenuSelection<Self>, _state: &Self::State) -> bool {
+        false
+    }
+
+    /// The current value of a slider
+    fn value(_action: &Self::Action, _state: &Self::State) -> f32 {
+        0.0
+    }
+
+    /// The current text of a text input
+    fn text(_action: &Self::Action, _state: &Self::State) -> String {
+        String::new()
+    }
+
+    /// The current input of a key binding
+    fn binding(_action: &Self::Action, _state: &Self::State) -> Option<BoundInput> {
+        None
+    }
+
+    /// Build the `Menu` of this screen from the definition. Items with unknown
+    /// ids are skipped, unknown screens result in an empty menu. These are
+    /// reported once per load by the `MenuDefinitionPlugin`, see
+    /// `definition_problems`.
+    fn resolve_definition(&self, state: &Self::State) -> Menu<Self> {
+        let definition = Self::definition(state);
+        let Some(screen) = definition.screens.get(self.screen_id()) else {
+            return Menu::new(self.screen_id().to_string(), Vec::new());
+        };
+
+        let action = |id: &str| Self::action_from_id(id);
+
+        let mut entries = Vec::with_capacity(screen.items.len());
+        for item in &screen.items {
+            let entry = match item {
+                ItemDefinition::Screen {
+                    label,
+                    screen,
+                    icon,
+                    disabled,
+                } => {
+                    let Some(screen) = Self::screen_from_id(screen) else {
+                        continue;
+                    };
+                    let disabled =
+                        *disabled || Self::disabled(&MenuSelection::Screen(screen), state);
+                    MenuItem::screen(label.as_str(), screen)
+                        .with_icon(definition.icon(icon))
+                        .disabled(disabled)
+                }
+                ItemDefinition::Action {
+                    label,
+                    action: id,
+                    icon,
+                    disabled,
+                    confirmation,
+                } => {
+                    let Some(action) = action(id) else {
+                        continue;
+                    };
+                    let disabled =
+                        *disabled || Self::disabled(&MenuSelection::Action(action), state);
+                    let mut item = MenuItem::action(label.as_str(), action)
+                        .with_icon(definition.icon(icon))
+                        .disabled(disabled);
+                    if let Some(text) = confirmation {
+                        item = item.with_confirmation(Confirmation::new(text.as_str()));
+                    }
+                    match Self::checked(&action, state) {
+                        Some(checked) => item.checked(checked),
+                        None => item,
+                    }
+                }
+                ItemDefinition::Slider {
+                    label,
+                    action: id,
+                    min,
+                    max,
+                    step,
+                    icon,
+                } => {
+                    let Some(action) = action(id) else {
+                        continue;
+                    };
+                    let value = Self::value(&action, state);
+                    MenuItem::slider(label.as_str(), action, value, *min..=*max, *step)
+                        .with_icon(definition.icon(icon))
+                }
+                ItemDefinition::TextInput {
+                    label,
+                    action: id,
+                    placeholder,
+                    max_length,
+                    filter,
+                    icon,
+                } => {
+                    let Some(action) = action(id) else {
+                        continue;
+                    };
+                    let item =
+                        MenuItem::text_input(label.as_str(), action, Self::text(&action, state))
+                            .with_placeholder(placeholder.as_str())
+                            .with_filter(filter.into())
+                            .with_icon(definition.icon(icon));
+                    match max_length {
+                        Some(max_length) => item.with_max_length(*max_length),
+                        None => item,
+                    }
+                }
+                ItemDefinition::KeyBinding {
+                    label,
+                    action: id,
+                    icon,
+                } => {
+                    let Some(action) = action(id) else {
+                        continue;
+                    };
+                    MenuItem::key_binding(label.as_str(), action, Self::binding(&action, state))
+                        .with_icon(definition.icon(icon))
+                }
+                ItemDefinition::Label { label, icon } => {
+                    MenuItem::label(label.as_str()).with_icon(definition.icon(icon))
+                }
+                ItemDefinition::Headline { label, icon } => {
+                    MenuItem::headline(label.as_str()).with_icon(definition.icon(icon))
+                }
+                ItemDefinition::Image { path } => match definition.images.get(path) {
+                    Some(handle) => MenuItem::image(handle.clone()),
+                    None => continue,
+                },
+            };
+            entries.push(entry);
+        }
+
+        let id = screen.id.as_deref().unwrap_or(self.screen_id());
+        let mut menu = Menu::new(id.to_string(), entries);
+        menu.visible_rows = screen.visible_rows;
+        menu.title = screen.title.as_deref().map(WidgetLabel::from);
+        menu
+    }
+
+    /// The ids of a definition that can't be resolved. `screens` have to be
+    /// defined in addition to the screens that are referenced by items,
+    /// e.g. the root screen.
+    fn definition_problems(
+        definition: &MenuDefinition,
+        screens: &[Self],
+    ) -> Vec<DefinitionProblem> {
+        let mut problems = Vec::new();
+        let mut report = |problem: DefinitionProblem| {
+            if !problems.contains(&problem) {
+                problems.push(problem);
+            }
+        };
+        for screen in screens {
+            if !definition.screens.contains_key(screen.screen_id()) {
+                report(DefinitionProblem::MissingScreen(
+                    screen.screen_id().to_string(),
+                ));
+            }
+        }
+        for item in definition.screens.values().flat_map(|screen| &screen.items) {
+            match item {
+                ItemDefinition::Screen { screen: id, .. } => match Self::screen_from_id(id) {
+                    None => report(DefinitionProblem::UnknownScreen(id.clone())),
+                    Some(screen) if !definition.screens.contains_key(screen.screen_id()) => report(
+                        DefinitionProblem::MissingScreen(screen.screen_id().to_string()),
+                    ),
+                    Some(_) => (),
+                },
+                ItemDefinition::Action { action: id, .. }
+                | ItemDefinition::Slider { action: id, .. }
+                | ItemDefinition::TextInput { action: id, .. }
+                | ItemDefinition::KeyBinding { action: id, .. } => {
+                    if Self::action_from_id(id).is_none() {
+                        report(DefinitionProblem::UnknownAction(id.clone()));
+                    }
+                }
+                ItemDefinition::Label { .. }
+                | ItemDefinition::Headline { .. }
+                | ItemDefinition::Image { .. } => (),
+            }
+        }
+        problems
+    }
+}
+
+/// An id of a `MenuDefinition` that can't be resolved
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DefinitionProblem {
+    /// The item is skipped
+    #[error("Unknown screen {0:?} in menu definition")]
+    UnknownScreen(String),
+    /// The item is skipped
+    #[error("Unknown action {0:?} in menu definition")]
+    UnknownAction(String),
+    /// The screen is shown as an empty menu
+    #[error("No menu definition for screen {0:?}")]
+    MissingScreen(String),
+}
+
+/// The definition file that is copied into the state of the menus, the
+/// `MenuState` and the `QuickMenu` instances, whenever it is loaded or
+/// changes on disk
+#[derive(Resource)]
+pub struct MenuDefinitionHandle(pub Handle<MenuDefinition>);
+
+/// Registers the `MenuDefinition` asset and keeps the state of the menus
+/// of type `S` in sync with the `MenuDefinitionHandle` resource.
+pub struct MenuDefinitionPlugin<S>
+where
+    S: MenuDefinitionAdapter + 'static,
+{
+    s: std::marker::PhantomData<S>,
+}
+
+impl<S> MenuDefinitionPlugin<S>
+where
+    S: MenuDefinitionAdapter + 'static,
+{
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            s: Default::default(),
+        }
+    }
+}
+
+impl<S> Plugin for MenuDefinitionPlugin<S>
+where
+    S: MenuDefinitionAdapter + 'static,
+{
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<Assets<MenuDefinition>>() {
+            app.init_asset::<MenuDefinition>()
+                .init_asset_loader::<MenuDefinitionLoader>();
+        }
+        app.add_systems(
+            Update,
+            definition_system::<S>
+                .before(redraw_system::<S>)
+                .run_if(menu_exists::<S>)
+                .run_if(resource_exists::<MenuDefinitionHandle>),
+        );
+    }
+}
+
+fn definition_system<S>(
+    mut asset_events: EventReader<AssetEvent<MenuDefinition>>,
+    definitions: Res<Assets<MenuDefinition>>,
+    handle: Res<MenuDefinitionHandle>,
+    mut menus: Menus<S>,
+) where
+    S: MenuDefinitionAdapter + 'static,
+{
+    let id = handle.0.id();
+    let changed = asset_events.read().fold(false, |changed, event| {
+        changed || event.is_loaded_with_dependencies(id) || event.is_modified(id)
+    }) || handle.is_changed();
+    let Some(definition) = definitions.get(id) else {
+        return;
+    };
+    // Also sync the menus that were opened after loading. Changing the
+    // state redraws the menu.
+    let mut screens = Vec::new();
+    menus.for_each(|_, context| {
+        let menu_state = context.menu_state;
+        if !changed && menu_state.initial_render_done {
+            return;
+        }
+        screens.extend_from_slice(menu_state.stack());
+        *S::definition_mut(menu_state.state_mut()) = definition.clone();
+    });
+    for problem in S::definition_problems(definition, &screens) {
+        warn!("{problem}");
+    }
+}
@@ -0,0 +1,174 @@
+//! Fluent localization
+//! Available with the `fluent` feature. Loads Fluent `.ftl` files as
+//! `FluentFile` assets and resolves localized labels with them. The language
+//! of a file is its file name, e.g. `locales/de.ftl` contains the German
+//! messages.
+//!
+//! ```ignore
+//! commands.insert_resource(FluentFiles(vec![
+//!     assets.load("locales/en.ftl"),
+//!     assets.load("locales/de.ftl"),
+//! ]));
+//! ```
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+use crate::localization::{LocalizationArg, MenuLocalization, MenuLocalizer};
+
+/// The messages of one language
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct FluentFile {
+    pub language: String,
+    pub source: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FluentFileLoaderError {
+    #[error("Could not read the fluent file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The fluent file is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+}
+
+/// Loads `FluentFile`s from `.ftl` files
+#[derive(Default)]
+pub struct FluentFileLoader;
+
+impl AssetLoader for FluentFileLoader {
+    type Asset = FluentFile;
+    type Settings = ();
+    type Error = FluentFileLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let language = load_context
+            .path()
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Ok(FluentFile {
+            language,
+            source: String::from_utf8(bytes)?,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+/// A `MenuLocalizer` with one Fluent bundle per language
+#[derive(Default)]
+pub struct FluentLocalizer {
+    bundles: HashMap<String, FluentBundle<FluentResource>>,
+}
+
+impl FluentLocalizer {
+    /// Add the messages of a `.ftl` source to a language. Invalid messages
+    /// are skipped and reported as warnings.
+    pub fn add_source(&mut self, language: &str, source: &str) {
+        let resource = match FluentResource::try_new(source.to_string()) {
+            Ok(resource) => resource,
+            Err((resource, errors)) => {
+                warn!("Invalid fluent messages for {language:?}: {errors:?}");
+                resource
+            }
+        };
+        let bundle = self.bundles.entry(language.to_string()).or_insert_with(|| {
+            let id = language.parse::<LanguageIdentifier>().unwrap_or_default();
+            let mut bundle = FluentBundle::new_concurrent(vec![id]);
+            // Bevy's text doesn't render the unicode isolation marks
+            bundle.set_use_isolating(false);
+            bundle
+        });
+        if let Err(errors) = bundle.add_resource(resource) {
+            warn!("Duplicate fluent messages for {language:?}: {errors:?}");
+        }
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.bundles.keys().map(String::as_str)
+    }
+}
+
+impl MenuLocalizer for FluentLocalizer {
+    fn localize(
+        &self,
+        language: &str,
+        key: &str,
+        args: &[(String, LocalizationArg)],
+    ) -> Option<String> {
+        let bundle = self.bundles.get(language)?;
+        let pattern = bundle.get_message(key)?.value()?;
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            let value = match value {
+                LocalizationArg::String(value) => FluentValue::from(value.as_str()),
+                LocalizationArg::Number(value) => FluentValue::from(*value),
+            };
+            fluent_args.set(name.as_str(), value);
+        }
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+        if !errors.is_empty() {
+            warn!("Could not format the fluent message {key:?}: {errors:?}");
+        }
+        Some(text.into_owned())
+    }
+}
+
+/// The `.ftl` files that make up the `MenuLocalization`. Whenever one of
+/// them is loaded or changes on disk, the localizer is rebuilt. The language
+/// is kept, a new `MenuLocalization` starts with the language of the first file.
+#[derive(Resource)]
+pub struct FluentFiles(pub Vec<Handle<FluentFile>>);
+
+pub(crate) fn fluent_system(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<FluentFile>>,
+    files: Res<Assets<FluentFile>>,
+    handles: Res<FluentFiles>,
+    localization: Option<ResMut<MenuLocalization>>,
+) {
+    // Read all events, so that they aren't seen again in the next frame
+    let changed = asset_events
+        .read()
+        .filter(|event| {
+            handles.0.iter().any(|handle| {
+                event.is_loaded_with_dependencies(handle.id()) || event.is_modified(handle.id())
+            })
+        })
+        .count()
+        > 0;
+    if !changed && !handles.is_changed() {
+        return;
+    }
+    let loaded: Vec<&FluentFile> = handles
+        .0
+        .iter()
+        .filter_map(|handle| files.get(handle.id()))
+        .collect();
+    let Some(first) = loaded.first() else {
+        return;
+    };
+    let mut localizer = FluentLocalizer::default();
+    for file in &loaded {
+        localizer.add_source(&file.language, &file.source);
+    }
+    match localization {
+        Some(mut localization) => localization.set_localizer(localizer),
+        None => commands.insert_resource(MenuLocalization::new(first.language.clone(), localizer)),
+    }
+}
@@ -0,0 +1,471 @@
+//! Input mapping
+//! Maps keys, gamepad buttons and gamepad axes onto `NavigationEvent`s.
+//! The `MenuInputMap` can be configured via `MenuOptions` or changed at
+//! runtime as a `Resource` in order to allow rebinding the menu controls.
+
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+use crate::types::NavigationEvent;
+
+/// Maps a gamepad axis onto a `NavigationEvent`. A negative threshold
+/// triggers when the axis value is below it, a positive one when the
+/// value is above it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisBinding {
+    pub axis: GamepadAxisType,
+    pub threshold: f32,
+    pub event: NavigationEvent,
+}
+
+impl AxisBinding {
+    pub fn new(axis: GamepadAxisType, threshold: f32, event: NavigationEvent) -> Self {
+        Self {
+            axis,
+            threshold,
+            event,
+        }
+    }
+
+    /// Is the axis active for the given value. Values within the dead zone
+    /// count as zero. Once `held`, the axis stays active until the value falls
+    /// back below the threshold by more than the hysteresis.
+    pub fn is_active(&self, value: f32, repeat: &RepeatSettings, held: bool) -> bool {
+        let value = if value.abs() < repeat.dead_zone {
+            0.0
+        } else {
+            value
+        };
+        let magnitude = if held {
+            (self.threshold.abs() - repeat.hysteresis).max(repeat.dead_zone)
+        } else {
+            self.threshold.abs()
+        };
+        if self.threshold < 0.0 {
+            value < -magnitude
+        } else {
+            value > magnitude
+        }
+    }
+}
+
+/// Controls how held keys, buttons and sticks repeat their `NavigationEvent`.
+/// Only directional events (`Up`, `Down`, `Left`, `Right`) repeat.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepeatSettings {
+    /// Seconds an input has to be held before it starts repeating
+    pub delay: f32,
+    /// Seconds between two repeated events
+    pub interval: f32,
+    /// Axis values below this are treated as zero
+    pub dead_zone: f32,
+    /// How far an axis has to fall back below its threshold to be released
+    pub hysteresis: f32,
+}
+
+impl Default for RepeatSettings {
+    fn default() -> Self {
+        Self {
+            delay: 0.4,
+            interval: 0.1,
+            dead_zone: 0.1,
+            hysteresis: 0.2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Resource)]
+pub struct MenuInputMap {
+    /// Keyboard keys. If multiple keys are pressed, the first one wins
+    pub keys: Vec<(KeyCode, NavigationEvent)>,
+    /// Buttons, for every connected gamepad
+    pub gamepad_buttons: Vec<(GamepadButtonType, NavigationEvent)>,
+    /// Axes, for every connected gamepad
+    pub axes: Vec<AxisBinding>,
+    /// Auto-repeat for held inputs
+    pub repeat: RepeatSettings,
+}
+
+impl Default for MenuInputMap {
+    fn default() -> Self {
+        use GamepadAxisType::*;
+        use GamepadButtonType::{DPadDown, DPadLeft, DPadRight, DPadUp, East, North, South, West};
+        use NavigationEvent::*;
+        Self {
+            keys: vec![
+                (KeyCode::ArrowDown, Down),
+                (KeyCode::ArrowUp, Up),
+                (KeyCode::ArrowLeft, Left),
+                (KeyCode::ArrowRight, Right),
+                (KeyCode::Enter, Select),
+                (KeyCode::Backspace, Back),
+            ],
+            gamepad_buttons: vec![
+                (DPadDown, Down),
+                (DPadUp, Up),
+                (DPadLeft, Left),
+                (DPadRight, Right),
+                (South, Select),
+                (West, Select),
+                (East, Back),
+                (North, Back),
+            ],
+            axes: vec![
+                AxisBinding::new(LeftStickX, -0.5, Left),
+                AxisBinding::new(LeftStickX, 0.5, Right),
+                AxisBinding::new(LeftStickY, -0.5, Down),
+                AxisBinding::new(LeftStickY, 0.5, Up),
+                AxisBinding::new(RightStickX, -0.5, Left),
+                AxisBinding::new(RightStickX, 0.5, Right),
+                AxisBinding::new(RightStickY, -0.5, Down),
+                AxisBinding::new(RightStickY, 0.5, Up),
+            ],
+            repeat: RepeatSettings::default(),
+        }
+    }
+}
+
+impl MenuInputMap {
+    /// A map without any bindings
+    pub fn empty() -> Self {
+        Self {
+            keys: Vec::new(),
+            gamepad_buttons: Vec::new(),
+            axes: Vec::new(),
+            repeat: RepeatSettings::default(),
+        }
+    }
+
+    pub fn with_repeat(mut self, repeat: RepeatSettings) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn with_key(mut self, key: KeyCode, event: NavigationEvent) -> Self {
+        self.keys.push((key, event));
+        self
+    }
+
+    pub fn with_gamepad_button(
+        mut self,
+        button: GamepadButtonType,
+        event: NavigationEvent,
+    ) -> Self {
+        self.gamepad_buttons.push((button, event));
+        self
+    }
+
+    pub fn with_axis(
+        mut self,
+        axis: GamepadAxisType,
+        threshold: f32,
+        event: NavigationEvent,
+    ) -> Self {
+        self.axes.push(AxisBinding::new(axis, threshold, event));
+        self
+    }
+
+    /// Remove all bindings for a key
+    pub fn without_key(mut self, key: KeyCode) -> Self {
+        self.keys.retain(|(k, _)| k != &key);
+        self
+    }
+
+    /// Remove all bindings for a gamepad button
+    pub fn without_gamepad_button(mut self, button: GamepadButtonType) -> Self {
+        self.gamepad_buttons.retain(|(b, _)| b != &button);
+        self
+    }
+
+    /// Remove all bindings for a gamepad axis
+    pub fn without_axis(mut self, axis: GamepadAxisType) -> Self {
+        self.axes.retain(|binding| binding.axis != axis);
+        self
+    }
+}
+
+/// An input that can be bound to an action with a `MenuItem::KeyBinding`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoundInput {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    GamepadButton(GamepadButtonType),
+    /// A gamepad axis in the positive or negative direction
+    GamepadAxis(GamepadAxisType, bool),
+}
+
+impl BoundInput {
+    /// A short, human readable name, e.g. `"W"`, `"Left Mouse"` or `"A"`
+    /// for the south gamepad button
+    pub fn label(&self) -> String {
+        match self {
+            BoundInput::Key(key) => Self::key_label(*key),
+            BoundInput::Mouse(button) => match button {
+                MouseButton::Left => "Left Mouse".to_string(),
+                MouseButton::Right => "Right Mouse".to_string(),
+                MouseButton::Middle => "Middle Mouse".to_string(),
+                MouseButton::Back => "Mouse Back".to_string(),
+                MouseButton::Forward => "Mouse Forward".to_string(),
+                MouseButton::Other(index) => format!("Mouse {index}"),
+            },
+            BoundInput::GamepadButton(button) => {
+                use GamepadButtonType::*;
+                match button {
+                    South => "A".to_string(),
+                    East => "B".to_string(),
+                    West => "X".to_string(),
+                    North => "Y".to_string(),
+                    LeftTrigger => "LB".to_string(),
+                    LeftTrigger2 => "LT".to_string(),
+                    RightTrigger => "RB".to_string(),
+                    RightTrigger2 => "RT".to_string(),
+                    LeftThumb => "L3".to_string(),
+                    RightThumb => "R3".to_string(),
+                    DPadUp => "D-Pad Up".to_string(),
+                    DPadDown => "D-Pad Down".to_string(),
+                    DPadLeft => "D-Pad Left".to_string(),
+                    DPadRight => "D-Pad Right".to_string(),
+                    Other(index) => format!("Button {index}"),
+                    other => format!("{other:?}"),
+                }
+            }
+            BoundInput::GamepadAxis(axis, positive) => {
+                use GamepadAxisType::*;
+                let (name, negative_direction, positive_direction) = match axis {
+                    LeftStickX => ("Left Stick".to_string(), "Left", "Right"),
+                    LeftStickY => ("Left Stick".to_string(), "Down", "Up"),
+                    RightStickX => ("Right Stick".to_string(), "Left", "Right"),
+                    RightStickY => ("Right Stick".to_string(), "Down", "Up"),
+                    LeftZ => ("Left Z".to_string(), "-", "+"),
+                    RightZ => ("Right Z".to_string(), "-", "+"),
+                    Other(index) => (format!("Axis {index}"), "-", "+"),
+                };
+                let direction = if *positive {
+                    positive_direction
+                } else {
+                    negative_direction
+                };
+                format!("{name} {direction}")
+            }
+        }
+    }
+
+    fn key_label(key: KeyCode) -> String {
+        match key {
+            KeyCode::ArrowUp => "Up".to_string(),
+            KeyCode::ArrowDown => "Down".to_string(),
+            KeyCode::ArrowLeft => "Left".to_string(),
+            KeyCode::ArrowRight => "Right".to_string(),
+            KeyCode::Escape => "Esc".to_string(),
+            KeyCode::ShiftLeft => "Left Shift".to_string(),
+            KeyCode::ShiftRight => "Right Shift".to_string(),
+            KeyCode::ControlLeft => "Left Ctrl".to_string(),
+            KeyCode::ControlRight => "Right Ctrl".to_string(),
+            KeyCode::AltLeft => "Left Alt".to_string(),
+            KeyCode::AltRight => "Right Alt".to_string(),
+            _ => {
+                let name = format!("{key:?}");
+                if let Some(rest) = name.strip_prefix("Numpad") {
+                    format!("Num {rest}")
+                } else if let Some(rest) = name
+                    .strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                {
+                    rest.to_string()
+                } else {
+                    name
+                }
+            }
+        }
+    }
+}
+
+/// The device that produced a navigation input. A `QuickMenu` can be
+/// restricted to one device, e.g. one gamepad per player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    Keyboard,
+    Gamepad(Gamepad),
+}
+
+/// The physical origin of a navigation input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum InputSource {
+    Key(KeyCode),
+    Button(GamepadButton),
+    /// A gamepad and the index of the `AxisBinding`
+    Axis(Gamepad, usize),
+}
+
+/// The repeat state machine for all currently held inputs
+#[derive(Default)]
+pub(crate) struct InputRepeat {
+    /// The seconds until the next repeat of each held input
+    held: HashMap<InputSource, f32>,
+    /// The inputs that were reported as held during the current frame
+    seen: HashSet<InputSource>,
+    /// The inputs that were reported as released during the previous frame
+    released: HashSet<InputSource>,
+    /// The inputs that were reported as released during the current frame
+    seen_released: HashSet<InputSource>,
+}
+
+impl InputRepeat {
+    pub(crate) fn is_held(&self, source: &InputSource) -> bool {
+        self.held.contains_key(source)
+    }
+
+    /// Is the input active now and was it released in the previous frame.
+    /// This is the fresh press for inputs without their own edge detection,
+    /// such as sticks. An input that is active in the first frame in which it
+    /// is reported doesn't count, as it may have been held before.
+    pub(crate) fn just_activated(&self, source: &InputSource, active: bool) -> bool {
+        active && self.released.contains(source)
+    }
+
+    /// Feed the current state of an input. Returns `true` if its event
+    /// should be sent this frame. A hold only starts with a fresh press, so
+    /// that inputs which were already held when the menu opened are ignored.
+    pub(crate) fn update(
+        &mut self,
+        source: InputSource,
+        pressed: bool,
+        just_pressed: bool,
+        event: NavigationEvent,
+        settings: &RepeatSettings,
+        delta: f32,
+    ) -> bool {
+        if !pressed {
+            self.held.remove(&source);
+            self.seen_released.insert(source);
+            return false;
+        }
+        let Some(remaining) = self.held.get_mut(&source) else {
+            if just_pressed {
+                self.seen.insert(source);
+                self.held.insert(source, settings.delay);
+            }
+            return just_pressed;
+        };
+        self.seen.insert(source);
+        if !event.is_directional() {
+            return false;
+        }
+        *remaining -= delta;
+        if *remaining > 0.0 {
+            return false;
+        }
+        // Don't catch up on missed repeats after a long frame
+        *remaining = (*remaining + settings.interval).max(0.0);
+        true
+    }
+
+    /// Forget inputs that were not reported this frame, e.g. because
+    /// the gamepad was disconnected
+    pub(crate) fn finish_frame(&mut self) {
+        let seen = std::mem::take(&mut self.seen);
+        self.held.retain(|source, _| seen.contains(source));
+        self.released = std::mem::take(&mut self.seen_released);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: f32 = 1.0 / 60.0;
+
+    fn stick() -> InputSource {
+        InputSource::Axis(Gamepad::new(0), 0)
+    }
+
+    /// Feed a stick for one frame the way `keyboard_input_system` does
+    fn feed_stick(repeat: &mut InputRepeat, active: bool, delta: f32) -> bool {
+        let just_pressed = repeat.just_activated(&stick(), active);
+        let sent = repeat.update(
+            stick(),
+            active,
+            just_pressed,
+            NavigationEvent::Down,
+            &RepeatSettings::default(),
+            delta,
+        );
+        repeat.finish_frame();
+        sent
+    }
+
+    #[test]
+    fn held_key_repeats_after_delay() {
+        let settings = RepeatSettings::default();
+        let source = InputSource::Key(KeyCode::ArrowDown);
+        let mut repeat = InputRepeat::default();
+        let mut update = |just_pressed, delta| {
+            let sent = repeat.update(
+                source,
+                true,
+                just_pressed,
+                NavigationEvent::Down,
+                &settings,
+                delta,
+            );
+            repeat.finish_frame();
+            sent
+        };
+        assert!(update(true, FRAME));
+        assert!(!update(false, 0.3));
+        assert!(update(false, 0.11));
+        assert!(!update(false, 0.05));
+        assert!(update(false, 0.05));
+    }
+
+    #[test]
+    fn select_does_not_repeat() {
+        let settings = RepeatSettings::default();
+        let source = InputSource::Key(KeyCode::Enter);
+        let mut repeat = InputRepeat::default();
+        assert!(repeat.update(
+            source,
+            true,
+            true,
+            NavigationEvent::Select,
+            &settings,
+            FRAME
+        ));
+        repeat.finish_frame();
+        assert!(!repeat.update(source, true, false, NavigationEvent::Select, &settings, 1.0));
+    }
+
+    #[test]
+    fn input_held_before_opening_is_ignored() {
+        let settings = RepeatSettings::default();
+        let source = InputSource::Key(KeyCode::ArrowDown);
+        let mut repeat = InputRepeat::default();
+        for _ in 0..60 {
+            assert!(!repeat.update(source, true, false, NavigationEvent::Down, &settings, FRAME));
+            repeat.finish_frame();
+        }
+    }
+
+    #[test]
+    fn stick_tilted_before_opening_is_ignored() {
+        let mut repeat = InputRepeat::default();
+        for _ in 0..60 {
+            assert!(!feed_stick(&mut repeat, true, FRAME));
+        }
+        // Released and tilted again
+        assert!(!feed_stick(&mut repeat, false, FRAME));
+        assert!(feed_stick(&mut repeat, true, FRAME));
+        assert!(!feed_stick(&mut repeat, true, FRAME));
+    }
+
+    #[test]
+    fn stick_hysteresis_keeps_the_hold() {
+        let settings = RepeatSettings::default();
+        let binding = AxisBinding::new(GamepadAxisType::LeftStickY, -0.5, NavigationEvent::Down);
+        assert!(!binding.is_active(-0.4, &settings, false));
+        assert!(binding.is_active(-0.6, &settings, false));
+        assert!(binding.is_active(-0.4, &settings, true));
+        assert!(!binding.is_active(-0.2, &settings, true));
+        assert!(!binding.is_active(0.9, &settings, true));
+    }
+}
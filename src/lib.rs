@@ -1,14 +1,26 @@
 #![doc = include_str!("../Readme.md")]
 
 // pub mod helpers;
+#[cfg(feature = "audio")]
+pub mod audio;
+pub mod definition;
+#[cfg(feature = "fluent")]
+pub mod fluent;
+pub mod input;
+pub mod localization;
 mod navigation_menu;
+pub mod snapshot;
 pub mod style;
 mod systems;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod types;
 mod widgets;
 
 use bevy::prelude::*;
-use style::Stylesheet;
+use input::{BoundInput, InputDevice};
+use localization::MenuLocalization;
+use style::{Stylesheet, StylesheetLoader};
 use types::{CleanUpUI, MenuAssets};
 
 use std::fmt::Debug;
@@ -16,8 +28,13 @@ use std::hash::Hash;
 
 pub use navigation_menu::NavigationMenu;
 pub use types::{
-    ButtonComponent, Menu, MenuIcon, MenuItem, MenuOptions, MenuSelection, NavigationEvent,
-    PrimaryMenu, RedrawEvent, RichTextEntry, Selections, VerticalMenuComponent,
+    Adjustment, BindingCapture, BindingConflict, ButtonComponent, CharacterFilter, Confirmation,
+    ConfirmationAnswer, DeviceNavigationEvent, FocusChanged, ItemDescription, KeyBindingValue,
+    Menu, MenuClosed, MenuFocus, MenuIcon, MenuId, MenuItem, MenuOpened, MenuOptions,
+    MenuResources, MenuSelection, MenuSound, MenuSounds, MenuWarning, NavigationEvent, PrimaryMenu,
+    RedrawEvent, RichTextEntry, ScreenPopped, ScreenPushed, ScrollOffset, ScrollOffsets,
+    Selections, SliderComponent, SliderValue, TextEditing, TextInputValue, VerticalMenuComponent,
+    WidgetLabel,
 };
 
 /// The quickmenu plugin.
@@ -190,27 +207,120 @@ where
     S: ScreenTrait<Action = A, State = State> + 'static,
 {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.insert_resource(self.options.unwrap_or_default())
-            .init_resource::<MenuAssets>()
-            .insert_resource(Selections::default())
+        let options = self.options.clone().unwrap_or_default();
+        if !app.world().contains_resource::<Assets<Stylesheet>>() {
+            app.init_asset::<Stylesheet>()
+                .init_asset_loader::<StylesheetLoader>();
+        }
+        let assets = MenuAssets::new(&options, app.world_mut());
+        app.insert_resource(MenuResources::<S>::new(assets))
             .add_event::<NavigationEvent>()
+            .add_event::<DeviceNavigationEvent>()
             .add_event::<RedrawEvent>()
+            .add_event::<MenuWarning>()
+            .add_event::<MenuOpened>()
+            .add_event::<MenuClosed>()
+            .add_event::<ScreenPushed<S>>()
+            .add_event::<ScreenPopped<S>>()
+            .add_event::<FocusChanged<S>>()
+            .add_event::<BindingConflict<S>>()
+            .add_event::<MenuSound>()
             .add_systems(
                 Update,
                 systems::cleanup_system::<S>.run_if(resource_exists::<CleanUpUI>),
             )
+            .add_systems(Update, systems::quick_menu_cleanup_system::<S>)
+            .add_systems(
+                Update,
+                systems::menu_focus_system::<S>.before(QuickMenuSet::Input),
+            )
             .add_systems(
                 Update,
                 (
-                    systems::mouse_system::<S>.run_if(resource_exists::<MenuState<S>>),
-                    systems::input_system::<S>.run_if(resource_exists::<MenuState<S>>),
-                    systems::redraw_system::<S>.run_if(resource_exists::<MenuState<S>>),
-                    systems::keyboard_input_system.run_if(resource_exists::<MenuState<S>>),
-                ),
+                    systems::mouse_system::<S>,
+                    systems::slider_drag_system::<S>,
+                    systems::scroll_system::<S>,
+                    systems::back_button_system::<S>,
+                    systems::breadcrumb_system::<S>,
+                    systems::dialog_button_system::<S>,
+                    systems::input_system::<S>.after(QuickMenuSet::Input),
+                    systems::text_input_system::<S>
+                        .after(QuickMenuSet::Input)
+                        .before(systems::input_system::<S>),
+                    systems::binding_capture_system::<S>
+                        .after(QuickMenuSet::Input)
+                        .before(systems::input_system::<S>)
+                        .before(systems::mouse_system::<S>),
+                    // The inputs are rendered in the same frame, so that the
+                    // keyboard sees what is being edited or captured
+                    systems::redraw_system::<S>
+                        .after(systems::input_system::<S>)
+                        .after(systems::mouse_system::<S>)
+                        .before(QuickMenuSet::Animation),
+                )
+                    .run_if(systems::menu_exists::<S>),
+            )
+            .add_systems(
+                Update,
+                systems::stylesheet_system::<S>.run_if(systems::menu_exists::<S>),
             );
+        // Shared by all menus, so that each input is only read once and each
+        // animation only advances once per frame
+        if !app.world().contains_resource::<QuickMenuShared>() {
+            app.init_resource::<QuickMenuShared>()
+                .insert_resource(options.input_map.clone())
+                .init_resource::<MenuFocus>()
+                .init_resource::<types::MirroredNavigation>()
+                .add_systems(
+                    Update,
+                    (
+                        systems::keyboard_input_system.in_set(QuickMenuSet::Input),
+                        (
+                            systems::screen_transition_system,
+                            systems::focus_transition_system,
+                            systems::tooltip_system,
+                        )
+                            .in_set(QuickMenuSet::Animation),
+                    ),
+                );
+        }
+        // Shared by all menus, so that each sound is only played once
+        #[cfg(feature = "audio")]
+        if !app.world().contains_resource::<audio::MenuSoundAssets>() {
+            let sounds = audio::MenuSoundAssets::new(&options.sounds, app.world());
+            app.insert_resource(sounds)
+                .add_systems(Update, audio::sound_system);
+        }
+        #[cfg(feature = "fluent")]
+        if !app
+            .world()
+            .contains_resource::<Assets<fluent::FluentFile>>()
+        {
+            app.init_asset::<fluent::FluentFile>()
+                .init_asset_loader::<fluent::FluentFileLoader>()
+                .add_systems(
+                    Update,
+                    fluent::fluent_system.run_if(resource_exists::<fluent::FluentFiles>),
+                );
+        }
     }
 }
 
+/// The systems that are shared by the menus of all screen types. The first
+/// `QuickMenuPlugin` adds them, so order your own systems against these sets
+/// instead of against the systems.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum QuickMenuSet {
+    /// Reads the keyboard and gamepads and sends the `DeviceNavigationEvent`s
+    Input,
+    /// Advances the screen, focus and tooltip animations
+    Animation,
+}
+
+/// Marks that the shared systems were added
+#[derive(Resource, Default)]
+struct QuickMenuShared;
+
 /// Remove the menu
 pub fn cleanup(commands: &mut Commands) {
     commands.init_resource::<CleanUpUI>();
@@ -222,6 +332,47 @@ pub trait ActionTrait: Debug + PartialEq + Eq + Clone + Copy + Hash + Send + Syn
     type State;
     type Event: Event + Send + Sync + 'static;
     fn handle(&self, state: &mut Self::State, event_writer: &mut EventWriter<Self::Event>);
+
+    /// Called when the item for this action is focused and the user navigates
+    /// left or right. Return `true` if the adjustment was consumed (e.g. a
+    /// volume was changed). Otherwise the horizontal input falls back to the
+    /// default navigation (`Left` goes back).
+    fn adjust(
+        &self,
+        _adjustment: Adjustment,
+        _state: &mut Self::State,
+        _event_writer: &mut EventWriter<Self::Event>,
+    ) -> bool {
+        false
+    }
+
+    /// Called when the value of a `MenuItem::Slider` with this action changes,
+    /// either via horizontal input or by dragging the slider with the mouse.
+    fn set_value(
+        &self,
+        _value: f32,
+        _state: &mut Self::State,
+        _event_writer: &mut EventWriter<Self::Event>,
+    ) {
+    }
+
+    /// Called when the text of a `MenuItem::TextInput` with this action is edited.
+    fn set_text(
+        &self,
+        _text: &str,
+        _state: &mut Self::State,
+        _event_writer: &mut EventWriter<Self::Event>,
+    ) {
+    }
+
+    /// Called when a `MenuItem::KeyBinding` with this action captured an input
+    fn set_binding(
+        &self,
+        _input: BoundInput,
+        _state: &mut Self::State,
+        _event_writer: &mut EventWriter<Self::Event>,
+    ) {
+    }
 }
 
 /// Each Menu / Screen uses this trait to define which menu items lead
@@ -240,6 +391,11 @@ where
 {
     menu: NavigationMenu<S>,
     pub initial_render_done: bool,
+    /// Set by changes to the menu that require a redraw
+    redraw_requested: bool,
+    /// The stack that the `ScreenPushed` and `ScreenPopped` events were
+    /// sent for
+    reported_stack: Vec<S>,
 }
 
 impl<S> MenuState<S>
@@ -250,14 +406,17 @@ where
         Self {
             menu: NavigationMenu::new(state, screen, sheet),
             initial_render_done: false,
+            redraw_requested: false,
+            reported_stack: vec![screen],
         }
     }
 
     /// Get a mutable reference to the state in order to change it.
     /// Changing something here will cause a re-render in the next frame.
-    /// Due to the way bevy works, just getting this reference, without actually performing
-    /// a change is enough to cause a re-render.
+    /// Just getting this reference, without actually performing a change
+    /// is enough to cause a re-render.
     pub fn state_mut(&mut self) -> &mut S::State {
+        self.redraw_requested = true;
         &mut self.menu.state
     }
 
@@ -265,4 +424,154 @@ where
     pub fn state(&self) -> &S::State {
         &self.menu.state
     }
+
+    /// A description of what is rendered for the given selections.
+    /// Localized labels are resolved if a `MenuLocalization` is given.
+    /// See [`snapshot::MenuSnapshot`].
+    pub fn snapshot(
+        &self,
+        selections: &Selections,
+        localization: Option<&MenuLocalization>,
+    ) -> snapshot::MenuSnapshot {
+        snapshot::MenuSnapshot::new(&self.menu, selections, localization)
+    }
+
+    /// The stylesheet the menu is rendered with
+    pub fn stylesheet(&self) -> &Stylesheet {
+        &self.menu.stylesheet
+    }
+
+    /// Replace the stylesheet. The menu is rebuilt in the next frame.
+    pub fn set_stylesheet(&mut self, sheet: Stylesheet) {
+        self.menu.stylesheet = sheet;
+        self.redraw_requested = true;
+    }
+
+    /// The screens on the stack, the top-most screen last
+    pub fn stack(&self) -> &[S] {
+        self.menu.stack()
+    }
+
+    /// The action whose confirmation dialog is open, if any
+    pub fn confirming(&self) -> Option<S::Action> {
+        self.menu.confirming()
+    }
+
+    /// The top-most, visible screen
+    pub fn current_screen(&self) -> Option<S> {
+        self.menu.stack().last().copied()
+    }
+
+    /// Open a screen on top of the current one, e.g. in order to jump
+    /// straight into a sub-screen. The menu is redrawn in the next frame.
+    pub fn push_screen(&mut self, screen: S) {
+        self.menu.push(screen);
+        self.redraw_requested = true;
+    }
+
+    /// Close the top-most screen and return it. The root screen can't be
+    /// closed, use `cleanup` to remove the menu instead.
+    pub fn pop_screen(&mut self) -> Option<S> {
+        let screen = self.menu.pop();
+        self.redraw_requested |= screen.is_some();
+        screen
+    }
+
+    /// Close all screens but the root screen
+    pub fn pop_to_root(&mut self) {
+        self.menu.pop_to_root();
+        self.redraw_requested = true;
+    }
+
+    /// Replace all screens, the first one becomes the new root screen.
+    /// An empty stack is ignored.
+    pub fn replace_stack(&mut self, stack: Vec<S>) {
+        self.menu.replace_stack(stack);
+        self.redraw_requested = true;
+    }
+}
+
+/// A menu instance on an entity. Unlike the `MenuState` resource, any number
+/// of instances can exist at the same time, even of the same screen type.
+/// Each one has its own stack, selections and stylesheet.
+///
+/// The menu is rendered as a child of the entity, so insert it together
+/// with a `NodeBundle`. Despawning the entity or removing the component
+/// removes the menu.
+#[derive(Component)]
+pub struct QuickMenu<S>
+where
+    S: ScreenTrait + 'static,
+{
+    menu_state: MenuState<S>,
+    selections: Selections,
+    scrolls: ScrollOffsets,
+    editing: TextEditing,
+    capture: BindingCapture,
+    /// Whether `NavigationEvent`s are applied to this menu while it has the
+    /// `MenuFocus` or receives the input of its device. The mouse always
+    /// works.
+    pub input_enabled: bool,
+    /// The device that controls this menu, e.g. the gamepad of a player in
+    /// split-screen, even without the focus. `None` accepts input from any
+    /// device while the menu has the focus.
+    pub input_device: Option<InputDevice>,
+}
+
+impl<S> QuickMenu<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub fn new(state: S::State, screen: S, sheet: Option<Stylesheet>) -> Self {
+        Self {
+            menu_state: MenuState::new(state, screen, sheet),
+            selections: Selections::default(),
+            scrolls: ScrollOffsets::default(),
+            editing: TextEditing::default(),
+            capture: BindingCapture::default(),
+            input_enabled: true,
+            input_device: None,
+        }
+    }
+
+    /// Only react to input from this device
+    pub fn with_input_device(mut self, device: InputDevice) -> Self {
+        self.input_device = Some(device);
+        self
+    }
+
+    pub fn with_input_enabled(mut self, enabled: bool) -> Self {
+        self.input_enabled = enabled;
+        self
+    }
+
+    pub fn menu_state(&self) -> &MenuState<S> {
+        &self.menu_state
+    }
+
+    /// Access the state, stack and stylesheet of this menu
+    pub fn menu_state_mut(&mut self) -> &mut MenuState<S> {
+        &mut self.menu_state
+    }
+
+    pub fn selections(&self) -> &Selections {
+        &self.selections
+    }
+
+    /// A description of what is rendered. See [`snapshot::MenuSnapshot`].
+    pub fn snapshot(&self, localization: Option<&MenuLocalization>) -> snapshot::MenuSnapshot {
+        self.menu_state.snapshot(&self.selections, localization)
+    }
+
+    pub(crate) fn context(&mut self) -> systems::MenuContext<'_, S> {
+        systems::MenuContext {
+            menu_state: &mut self.menu_state,
+            selections: &mut self.selections,
+            scrolls: &mut self.scrolls,
+            editing: &mut self.editing,
+            capture: &mut self.capture,
+            input_enabled: self.input_enabled,
+            input_device: self.input_device,
+        }
+    }
 }
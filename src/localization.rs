@@ -0,0 +1,122 @@
+//! Localization
+//! Labels can be given as message keys via `WidgetLabel::Localized`. They are
+//! resolved at render time by the `MenuLocalizer` of the `MenuLocalization`
+//! resource. Changing the language of the resource redraws all menus.
+//! With the `fluent` feature, `fluent::FluentLocalizer` reads the messages
+//! from Fluent `.ftl` files.
+
+use bevy::prelude::*;
+
+/// The value of an argument of a localized label
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocalizationArg {
+    String(String),
+    /// Numbers can be used for plural rules
+    Number(f64),
+}
+
+impl std::fmt::Display for LocalizationArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocalizationArg::String(value) => f.write_str(value),
+            LocalizationArg::Number(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<&str> for LocalizationArg {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for LocalizationArg {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<f64> for LocalizationArg {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<f32> for LocalizationArg {
+    fn from(value: f32) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<i32> for LocalizationArg {
+    fn from(value: i32) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<usize> for LocalizationArg {
+    fn from(value: usize) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+/// Resolves the message keys of localized labels. It is implemented for
+/// closures with the same signature as `localize`.
+pub trait MenuLocalizer: Send + Sync + 'static {
+    /// The text of the message `key` in `language`, `None` if it is missing
+    fn localize(
+        &self,
+        language: &str,
+        key: &str,
+        args: &[(String, LocalizationArg)],
+    ) -> Option<String>;
+}
+
+impl<F> MenuLocalizer for F
+where
+    F: Fn(&str, &str, &[(String, LocalizationArg)]) -> Option<String> + Send + Sync + 'static,
+{
+    fn localize(
+        &self,
+        language: &str,
+        key: &str,
+        args: &[(String, LocalizationArg)],
+    ) -> Option<String> {
+        self(language, key, args)
+    }
+}
+
+/// The localizer and the current language. Without this resource, or for
+/// missing messages, localized labels show their key.
+#[derive(Resource)]
+pub struct MenuLocalization {
+    language: String,
+    localizer: Box<dyn MenuLocalizer>,
+}
+
+impl MenuLocalization {
+    pub fn new(language: impl Into<String>, localizer: impl MenuLocalizer) -> Self {
+        Self {
+            language: language.into(),
+            localizer: Box::new(localizer),
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Switch the language. All menus are redrawn in the next frame.
+    pub fn set_language(&mut self, language: impl Into<String>) {
+        self.language = language.into();
+    }
+
+    /// Replace the localizer, e.g. after the messages were reloaded
+    pub fn set_localizer(&mut self, localizer: impl MenuLocalizer) {
+        self.localizer = Box::new(localizer);
+    }
+
+    pub fn localize(&self, key: &str, args: &[(String, LocalizationArg)]) -> Option<String> {
+        self.localizer.localize(&self.language, key, args)
+    }
+}
@@ -1,21 +1,58 @@
 //! Navigation Menu
 //! This is the primary horizontal menu which is used to host the various
 //! screens / vertical menus.
+use bevy::ecs::system::SystemParam;
+use bevy::input::keyboard::Key;
 use bevy::prelude::EventWriter;
 use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
 use std::fmt::Debug;
 
 use crate::{
-    style::Stylesheet,
-    types::{MenuAssets, PrimaryMenu, QuickMenuComponent},
+    input::BoundInput,
+    localization::MenuLocalization,
+    style::{DescriptionPosition, MenuLayout, Stylesheet},
+    types::{
+        Adjustment, BindingCapture, BindingConflict, Confirmation, ConfirmationAnswer, MenuAssets,
+        MenuOwner, MenuSound, MenuTransition, MenuWarning, OpenDialog, PrimaryMenu,
+        QuickMenuComponent, RenderedDescription, RenderedDialog, RenderedHeader, RenderedItem,
+        RenderedMenu, RenderedStylesheet, ScrollOffsets, TextEditing, WidgetId,
+    },
+    widgets::{DescriptionWidget, DialogWidget, ExistingMenu, HeaderWidget, Widget},
     Selections,
 };
 
 use super::{
-    types::{MenuSelection, NavigationEvent},
+    types::{MenuItem, MenuSelection, NavigationEvent},
     ActionTrait, ScreenTrait,
 };
 
+/// The currently rendered entities of the menu
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+pub struct RenderedTree<'w, 's, S>
+where
+    S: ScreenTrait + 'static,
+{
+    primary: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static MenuOwner<S>,
+            &'static RenderedStylesheet,
+            Option<&'static Children>,
+        ),
+        With<PrimaryMenu>,
+    >,
+    menus: Query<'w, 's, (&'static RenderedMenu, Option<&'static Children>)>,
+    items: Query<'w, 's, &'static RenderedItem<S>>,
+    transitions: Query<'w, 's, &'static MenuTransition>,
+    headers: Query<'w, 's, &'static RenderedHeader<S>>,
+    descriptions: Query<'w, 's, &'static RenderedDescription>,
+    dialogs: Query<'w, 's, &'static RenderedDialog>,
+}
+
 #[derive(Debug)]
 pub struct NavigationMenu<S>
 where
@@ -27,6 +64,10 @@ where
     pub(crate) state: S::State,
     /// The style to use
     pub(crate) stylesheet: Stylesheet,
+    /// The confirmation dialog that traps the focus
+    dialog: Option<OpenDialog<S>>,
+    /// The empty screen that was reported last, see `empty_screen_warning`
+    reported_empty_screen: Option<WidgetId>,
 }
 
 impl<S> NavigationMenu<S>
@@ -38,6 +79,8 @@ where
             stack: vec![root],
             state,
             stylesheet: sheet.unwrap_or_default(),
+            dialog: None,
+            reported_empty_screen: None,
         }
     }
 }
@@ -46,7 +89,326 @@ impl<S> NavigationMenu<S>
 where
     S: ScreenTrait + 'static,
 {
-    pub fn show(&self, assets: &MenuAssets, selections: &Selections, commands: &mut Commands) {
+    /// Render the menu. If it was already rendered, the existing entities
+    /// are updated and only changed parts are re-created. The menu of a
+    /// `QuickMenu` instance is rendered as a child of its `owner`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn show(
+        &self,
+        owner: Option<Entity>,
+        assets: &MenuAssets,
+        selections: &Selections,
+        scrolls: &mut ScrollOffsets,
+        editing: &TextEditing,
+        capture: &BindingCapture,
+        localization: Option<&MenuLocalization>,
+        tree: &RenderedTree<S>,
+        commands: &mut Commands,
+    ) {
+        let mut primaries = tree
+            .primary
+            .iter()
+            .filter(|(_, rendered_owner, _, _)| rendered_owner.owner == owner);
+        let existing = match (primaries.next(), primaries.next()) {
+            (Some((entity, _, rendered, children)), None) if rendered.0 == self.stylesheet => {
+                Some((entity, children.map(|c| &**c).unwrap_or(&[])))
+            }
+            _ => {
+                for (entity, rendered_owner, _, _) in tree.primary.iter() {
+                    if rendered_owner.owner == owner {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                }
+                None
+            }
+        };
+
+        let animated = existing.is_some() && self.stylesheet.transitions.animates_screens();
+        let (primary, rendered) = match existing {
+            Some(existing) => existing,
+            None => (self.spawn_primary(owner, commands), &[][..]),
+        };
+        let (headers, rendered_menus): (Vec<Entity>, Vec<Entity>) = rendered
+            .iter()
+            .partition(|entity| tree.headers.contains(**entity));
+        let (descriptions, rendered_menus): (Vec<Entity>, Vec<Entity>) = rendered_menus
+            .into_iter()
+            .partition(|entity| tree.descriptions.contains(*entity));
+        let (dialogs, rendered_menus): (Vec<Entity>, Vec<Entity>) = rendered_menus
+            .into_iter()
+            .partition(|entity| tree.dialogs.contains(*entity));
+        // Menus that are still leaving stay at the end of the row
+        let (leaving, menus): (Vec<Entity>, Vec<Entity>) =
+            rendered_menus.into_iter().partition(|entity| {
+                tree.transitions
+                    .get(*entity)
+                    .is_ok_and(|transition| transition.leaving)
+            });
+
+        let mut children = Vec::with_capacity(self.stack.len() + 1);
+        let header = self.header(localization);
+        let existing_header = headers
+            .iter()
+            .copied()
+            .find(|entity| tree.headers.get(*entity).ok() == header.as_ref());
+        for stale in headers
+            .iter()
+            .filter(|entity| Some(**entity) != existing_header)
+        {
+            commands.entity(*stale).despawn_recursive();
+        }
+        if let Some(header) = header {
+            children.push(existing_header.unwrap_or_else(|| {
+                commands
+                    .spawn(NodeBundle {
+                        // Takes a line of its own above the columns
+                        style: Style {
+                            width: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::FlexStart,
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        HeaderWidget::new(&header, &self.stylesheet).build(parent, assets)
+                    })
+                    .insert(header)
+                    .id()
+            }));
+        }
+
+        let mut description = None;
+        for (index, entry) in self.visible_screens().iter().enumerate() {
+            let mut menu_desc = entry.resolve(&self.state);
+            if let Some(localization) = localization {
+                for item in menu_desc.entries.iter_mut() {
+                    item.localize(localization);
+                }
+            }
+            // The top-most screen comes last
+            description = super::widgets::VerticalMenu::focused_description(
+                &menu_desc.id,
+                &menu_desc.entries,
+                selections,
+            )
+            .cloned();
+            let editing = editing
+                .target
+                .as_ref()
+                .or(capture.target.as_ref())
+                .filter(|(id, _)| id == &menu_desc.id)
+                .map(|(_, index)| *index);
+            let existing = menus.get(index).and_then(|entity| {
+                let (rendered, rows) = tree.menus.get(*entity).ok()?;
+                Some(ExistingMenu {
+                    entity: *entity,
+                    rendered,
+                    rows: rows.map(|c| &**c).unwrap_or(&[]),
+                })
+            });
+            let entity = super::widgets::VerticalMenu {
+                id: menu_desc.id,
+                items: &menu_desc.entries,
+                stylesheet: &self.stylesheet,
+                assets,
+                style: menu_desc.style.as_ref(),
+                background: menu_desc.background.as_ref(),
+                visible_rows: menu_desc.visible_rows,
+                editing,
+            }
+            .build(selections, scrolls, existing, &tree.items, commands);
+            if animated && menus.get(index) != Some(&entity) {
+                commands
+                    .entity(entity)
+                    .insert(MenuTransition::new(&self.stylesheet.transitions, false));
+            }
+            children.push(entity);
+        }
+
+        for stale in menus.iter().skip(self.visible_screens().len()) {
+            if animated {
+                let mut transition = MenuTransition::new(&self.stylesheet.transitions, true);
+                // A menu that is still entering leaves from where it is
+                if let Ok(entering) = tree.transitions.get(*stale) {
+                    transition.elapsed =
+                        transition.duration * (1.0 - entering.elapsed / entering.duration);
+                    transition.left = entering.left;
+                }
+                commands.entity(*stale).insert(transition);
+                children.push(*stale);
+            } else {
+                commands.entity(*stale).despawn_recursive();
+            }
+        }
+        children.extend(leaving);
+
+        // The description panel comes last, so that a side panel is
+        // to the right of all screens
+        let rendered_description = self
+            .stylesheet
+            .description
+            .as_ref()
+            .map(|_| RenderedDescription(description));
+        let existing_description = descriptions
+            .iter()
+            .copied()
+            .find(|entity| tree.descriptions.get(*entity).ok() == rendered_description.as_ref());
+        for stale in descriptions
+            .iter()
+            .filter(|entity| Some(**entity) != existing_description)
+        {
+            commands.entity(*stale).despawn_recursive();
+        }
+        if let Some(rendered_description) = rendered_description {
+            children.push(
+                existing_description.unwrap_or_else(|| {
+                    self.spawn_description(rendered_description, assets, commands)
+                }),
+            );
+        }
+
+        let rendered_dialog = self.dialog.as_ref().map(|dialog| {
+            let mut confirmation = dialog.confirmation.clone();
+            if let Some(localization) = localization {
+                confirmation.localize(localization);
+            }
+            RenderedDialog {
+                confirmation,
+                focus: dialog.focus,
+            }
+        });
+        let existing_dialog = dialogs
+            .iter()
+            .copied()
+            .find(|entity| tree.dialogs.get(*entity).ok() == rendered_dialog.as_ref());
+        for stale in dialogs
+            .iter()
+            .filter(|entity| Some(**entity) != existing_dialog)
+        {
+            commands.entity(*stale).despawn_recursive();
+        }
+        if let Some(rendered_dialog) = rendered_dialog {
+            children.push(existing_dialog.unwrap_or_else(|| {
+                commands
+                    .spawn(NodeBundle {
+                        // Covers the whole menu and blocks the mouse
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(0.0),
+                            top: Val::Px(0.0),
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: BackgroundColor(self.stylesheet.dialog.overlay),
+                        focus_policy: FocusPolicy::Block,
+                        z_index: ZIndex::Global(i32::MAX),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        DialogWidget::new(&rendered_dialog, &self.stylesheet).build(parent, assets)
+                    })
+                    .insert(rendered_dialog)
+                    .id()
+            }));
+        }
+        if children != rendered {
+            commands.entity(primary).replace_children(&children);
+        }
+    }
+
+    /// The screens that are rendered, depending on the layout
+    fn visible_screens(&self) -> &[S] {
+        match self.stylesheet.layout {
+            MenuLayout::Columns => &self.stack,
+            MenuLayout::SingleScreen(_) => &self.stack[self.stack.len() - 1..],
+        }
+    }
+
+    /// The breadcrumb and the back button above the screens, if there
+    /// is anything to show
+    fn header(&self, localization: Option<&MenuLocalization>) -> Option<RenderedHeader<S>> {
+        let crumbs = match self.stylesheet.breadcrumb {
+            Some(_) => self
+                .stack
+                .iter()
+                .enumerate()
+                .filter_map(|(index, screen)| {
+                    let mut title = screen.resolve(&self.state).title?;
+                    if let Some(localization) = localization {
+                        title.localize(localization);
+                    }
+                    // The entry of the next screen is what `pop_to_selection`
+                    // goes back to
+                    let selection = self.stack.get(index + 1).map(|s| MenuSelection::Screen(*s));
+                    Some((title, selection))
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let back = match &self.stylesheet.layout {
+            MenuLayout::SingleScreen(layout) if self.stack.len() > 1 => layout.back_button.clone(),
+            _ => None,
+        };
+        if crumbs.is_empty() && back.is_none() {
+            return None;
+        }
+        Some(RenderedHeader { crumbs, back })
+    }
+
+    fn spawn_description(
+        &self,
+        rendered: RenderedDescription,
+        assets: &MenuAssets,
+        commands: &mut Commands,
+    ) -> Entity {
+        let panel = self.stylesheet.description.clone().unwrap_or_default();
+        let style = match panel.position {
+            DescriptionPosition::Bottom => Style {
+                // Takes a line of its own below the columns
+                width: Val::Percent(100.0),
+                min_height: panel.size,
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            DescriptionPosition::Side => Style {
+                width: panel.size,
+                align_self: AlignSelf::Stretch,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexStart,
+                ..default()
+            },
+        };
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    margin: panel.margin,
+                    padding: panel.padding,
+                    ..style
+                },
+                background_color: BackgroundColor(panel.background),
+                ..default()
+            })
+            .with_children(|parent| {
+                if let Some(description) = &rendered.0 {
+                    DescriptionWidget::new(
+                        description,
+                        panel.text,
+                        panel.font_size,
+                        panel.image_size,
+                    )
+                    .build(parent, assets)
+                }
+            })
+            .insert(rendered)
+            .id()
+    }
+
+    fn spawn_primary(&self, owner: Option<Entity>, commands: &mut Commands) -> Entity {
         let style = self
             .stylesheet
             .style
@@ -54,7 +416,21 @@ where
             .cloned()
             .unwrap_or_else(|| Style {
                 align_items: AlignItems::FlexStart,
-                flex_direction: FlexDirection::Row,
+                flex_direction: match (&self.stylesheet.layout, &self.stylesheet.description) {
+                    (MenuLayout::Columns, _) => FlexDirection::Row,
+                    (MenuLayout::SingleScreen(_), Some(panel))
+                        if panel.position == DescriptionPosition::Side =>
+                    {
+                        FlexDirection::Row
+                    }
+                    (MenuLayout::SingleScreen(_), _) => FlexDirection::Column,
+                },
+                // The header and a bottom description panel wrap onto lines
+                // of their own
+                flex_wrap: match (&self.stylesheet.breadcrumb, &self.stylesheet.description) {
+                    (None, None) => FlexWrap::NoWrap,
+                    _ => FlexWrap::Wrap,
+                },
                 padding: UiRect::all(Val::Px(self.stylesheet.vertical_spacing)),
                 ..default()
             });
@@ -64,28 +440,21 @@ where
             .background
             .unwrap_or_else(|| Color::NONE.into());
 
-        commands
+        let primary = commands
             .spawn(NodeBundle {
                 style,
                 background_color,
                 ..default()
             })
             .insert(PrimaryMenu)
-            .with_children(|parent| {
-                for entry in self.stack.iter() {
-                    let menu_desc = entry.resolve(&self.state);
-                    super::widgets::VerticalMenu {
-                        id: menu_desc.id,
-                        items: &menu_desc.entries,
-                        stylesheet: &self.stylesheet,
-                        assets,
-                        style: menu_desc.style.as_ref(),
-                        background: menu_desc.background.as_ref(),
-                    }
-                    .build(selections, parent);
-                }
-            })
-            .insert(QuickMenuComponent);
+            .insert(RenderedStylesheet(self.stylesheet.clone()))
+            .insert(QuickMenuComponent)
+            .insert(MenuOwner::<S>::new(owner))
+            .id();
+        if let Some(owner) = owner {
+            commands.entity(owner).add_child(primary);
+        }
+        primary
     }
 
     pub fn apply_event(
@@ -93,7 +462,7 @@ where
         event: &NavigationEvent,
         selections: &mut Selections,
     ) -> Option<MenuSelection<S>> {
-        if self.stack.len() > 1 && &NavigationEvent::Back == event {
+        if self.stack.len() > 1 && matches!(event, NavigationEvent::Back | NavigationEvent::Left) {
             self.stack.pop();
         }
         for (index, entry) in self.stack.iter().enumerate() {
@@ -111,15 +480,336 @@ where
         None
     }
 
+    /// Offer an adjustment to the focused action of the top-most screen.
+    /// Returns `true` if the action consumed it.
+    pub fn apply_adjustment(
+        &mut self,
+        adjustment: Adjustment,
+        selections: &Selections,
+        event_writer: &mut EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    ) -> bool {
+        let Some(entry) = self.stack.last() else {
+            return false;
+        };
+        let menu_desc = entry.resolve(&self.state);
+        let Some((_, item)) = super::widgets::VerticalMenu::focused_entry(
+            &menu_desc.id,
+            &menu_desc.entries,
+            selections,
+        ) else {
+            return false;
+        };
+        if item.is_disabled() {
+            return false;
+        }
+        match item.inner() {
+            MenuItem::Slider(_, _, a, slider) => {
+                let value = slider.adjusted(adjustment);
+                if value != slider.value {
+                    a.set_value(value, &mut self.state, event_writer);
+                }
+                true
+            }
+            MenuItem::Action(_, _, a) => a.adjust(adjustment, &mut self.state, event_writer),
+            _ => false,
+        }
+    }
+
+    /// Clamp the selections of all stacked screens that are out of range,
+    /// e.g. because a screen has fewer items than before.
+    pub(crate) fn clamp_selections(&self, selections: &mut Selections) -> Vec<MenuWarning> {
+        let mut warnings = Vec::new();
+        for entry in self.stack.iter() {
+            let menu_desc = entry.resolve(&self.state);
+            let count = super::widgets::VerticalMenu::selectable_count(&menu_desc.entries);
+            let Some(index) = selections.0.get(&menu_desc.id).copied() else {
+                continue;
+            };
+            if count == 0 {
+                selections.0.remove(&menu_desc.id);
+            } else if index >= count {
+                selections.0.insert(menu_desc.id.clone(), count - 1);
+                warnings.push(MenuWarning::StaleSelection {
+                    menu: menu_desc.id,
+                    index,
+                    selectables: count,
+                });
+            }
+        }
+        warnings
+    }
+
+    /// The id of the top-most screen, if it has no selectable items
+    pub(crate) fn empty_screen(&self) -> Option<WidgetId> {
+        let menu_desc = self.stack.last()?.resolve(&self.state);
+        let count = super::widgets::VerticalMenu::selectable_count(&menu_desc.entries);
+        (count == 0).then_some(menu_desc.id)
+    }
+
+    /// A `MenuWarning::NoSelectableItems` if the top-most screen has no
+    /// selectable items. It is only returned once until another screen is
+    /// shown or the screen gets selectable items again.
+    pub(crate) fn empty_screen_warning(&mut self) -> Option<MenuWarning> {
+        let empty = self.empty_screen();
+        if empty == self.reported_empty_screen {
+            return None;
+        }
+        self.reported_empty_screen = empty.clone();
+        empty.map(MenuWarning::NoSelectableItems)
+    }
+
+    pub(crate) fn push(&mut self, screen: S) {
+        self.stack.push(screen);
+    }
+
+    /// Pop the top-most screen. The root screen is never popped.
+    pub(crate) fn pop(&mut self) -> Option<S> {
+        if self.stack.len() > 1 {
+            self.stack.pop()
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn pop_to_root(&mut self) {
+        self.stack.truncate(1);
+    }
+
+    /// Replace the stack. An empty stack is ignored.
+    pub(crate) fn replace_stack(&mut self, stack: Vec<S>) {
+        if !stack.is_empty() {
+            self.stack = stack;
+        }
+    }
+
+    /// The focused item of the top-most screen, with its wrappers
+    pub(crate) fn focused_item(&self, selections: &Selections) -> Option<MenuItem<S>> {
+        let menu_desc = self.stack.last()?.resolve(&self.state);
+        super::widgets::VerticalMenu::focused_entry(&menu_desc.id, &menu_desc.entries, selections)
+            .map(|(_, item)| item.clone())
+    }
+
+    /// The identifier and text of the focused item of the top-most screen,
+    /// if it is a `MenuItem::TextInput`
+    pub fn focused_text_input(
+        &self,
+        selections: &Selections,
+    ) -> Option<((WidgetId, usize), String)> {
+        let menu_desc = self.stack.last()?.resolve(&self.state);
+        match super::widgets::VerticalMenu::focused_item(
+            &menu_desc.id,
+            &menu_desc.entries,
+            selections,
+        ) {
+            Some((index, MenuItem::TextInput(_, _, _, value))) => {
+                Some(((menu_desc.id, index), value.text.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// The identifier of the focused item of the top-most screen,
+    /// if it is a `MenuItem::KeyBinding`
+    pub fn focused_key_binding(&self, selections: &Selections) -> Option<(WidgetId, usize)> {
+        let menu_desc = self.stack.last()?.resolve(&self.state);
+        match super::widgets::VerticalMenu::focused_item(
+            &menu_desc.id,
+            &menu_desc.entries,
+            selections,
+        ) {
+            Some((index, MenuItem::KeyBinding(..))) => Some((menu_desc.id, index)),
+            _ => None,
+        }
+    }
+
+    /// Bind an input to the focused key binding of the top-most screen.
+    /// Returns the conflict if other key bindings of the screen already
+    /// use the input.
+    pub fn apply_binding(
+        &mut self,
+        input: BoundInput,
+        selections: &Selections,
+        event_writer: &mut EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    ) -> Option<BindingConflict<S>> {
+        let menu_desc = self.stack.last()?.resolve(&self.state);
+        let Some((_, MenuItem::KeyBinding(_, _, action, _))) =
+            super::widgets::VerticalMenu::focused_item(
+                &menu_desc.id,
+                &menu_desc.entries,
+                selections,
+            )
+        else {
+            return None;
+        };
+        let action = *action;
+        // The focused binding is skipped by its action, the index of
+        // `focused_item` only counts the selectable items
+        let conflicts: Vec<S::Action> = menu_desc
+            .entries
+            .iter()
+            .filter_map(|item| match item.inner() {
+                MenuItem::KeyBinding(_, _, other, value)
+                    if *other != action && value.input == Some(input) =>
+                {
+                    Some(*other)
+                }
+                _ => None,
+            })
+            .collect();
+        action.set_binding(input, &mut self.state, event_writer);
+        if conflicts.is_empty() {
+            return None;
+        }
+        Some(BindingConflict {
+            action,
+            input,
+            conflicts,
+        })
+    }
+
+    /// Apply typed keys to the focused text input of the top-most screen.
+    /// Returns `true` if editing was finished via `Enter` or `Escape`.
+    /// `Escape` restores the `original` text.
+    pub fn apply_text_input(
+        &mut self,
+        keys: &[Key],
+        original: &str,
+        selections: &Selections,
+        event_writer: &mut EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    ) -> bool {
+        let Some(entry) = self.stack.last() else {
+            return true;
+        };
+        let menu_desc = entry.resolve(&self.state);
+        let Some((_, MenuItem::TextInput(_, _, action, value))) =
+            super::widgets::VerticalMenu::focused_item(
+                &menu_desc.id,
+                &menu_desc.entries,
+                selections,
+            )
+        else {
+            return true;
+        };
+
+        let mut edited = value.clone();
+        let mut finished = false;
+        for key in keys {
+            match key {
+                Key::Character(chars) => {
+                    for c in chars.chars() {
+                        edited.insert(c);
+                    }
+                }
+                Key::Space => {
+                    edited.insert(' ');
+                }
+                Key::Backspace => {
+                    edited.delete();
+                }
+                Key::Enter => {
+                    finished = true;
+                    break;
+                }
+                Key::Escape => {
+                    edited.text = original.to_string();
+                    finished = true;
+                    break;
+                }
+                _ => (),
+            }
+        }
+        if edited.text != value.text {
+            action.set_text(&edited.text, &mut self.state, event_writer);
+        }
+        finished
+    }
+
+    /// Handle a selected action or push a selected screen. Actions that
+    /// require a confirmation open the confirmation dialog instead.
     pub fn handle_selection(
         &mut self,
         selection: &MenuSelection<S>,
         event_writer: &mut EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
     ) {
         match selection {
-            MenuSelection::Action(a) => a.handle(&mut self.state, event_writer),
+            MenuSelection::Action(a) => match self.confirmation(selection) {
+                Some(confirmation) => {
+                    self.dialog = Some(OpenDialog {
+                        action: *a,
+                        focus: confirmation.focus,
+                        confirmation,
+                    });
+                }
+                None => a.handle(&mut self.state, event_writer),
+            },
             MenuSelection::Screen(s) => self.stack.push(*s),
-            MenuSelection::None => (),
+            // Sliders are only changed via adjustments
+            MenuSelection::Slider(_) | MenuSelection::None => (),
+        }
+    }
+
+    /// The confirmation of the item of the top-most screen with this selection
+    fn confirmation(&self, selection: &MenuSelection<S>) -> Option<Confirmation> {
+        let menu_desc = self.stack.last()?.resolve(&self.state);
+        menu_desc
+            .entries
+            .iter()
+            .find(|item| &item.as_selection() == selection)
+            .and_then(|item| item.confirmation())
+            .cloned()
+    }
+
+    /// The action whose confirmation dialog is open
+    pub(crate) fn confirming(&self) -> Option<S::Action> {
+        self.dialog.as_ref().map(|dialog| dialog.action)
+    }
+
+    pub(crate) fn dialog(&self) -> Option<&OpenDialog<S>> {
+        self.dialog.as_ref()
+    }
+
+    /// Focus a button of the open confirmation dialog
+    pub(crate) fn focus_dialog(&mut self, answer: ConfirmationAnswer) {
+        if let Some(dialog) = &mut self.dialog {
+            dialog.focus = answer;
+        }
+    }
+
+    /// Apply a navigation event to the open confirmation dialog. The
+    /// directions switch between the buttons, `Select` answers and `Back`
+    /// answers `No`. The action is only handled for `Yes`. Returns the
+    /// sound to play, if any.
+    pub(crate) fn apply_dialog_event(
+        &mut self,
+        event: &NavigationEvent,
+        event_writer: &mut EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    ) -> Option<MenuSound> {
+        let dialog = self.dialog.as_mut()?;
+        match event {
+            NavigationEvent::Up
+            | NavigationEvent::Down
+            | NavigationEvent::Left
+            | NavigationEvent::Right => {
+                dialog.focus = match dialog.focus {
+                    ConfirmationAnswer::Yes => ConfirmationAnswer::No,
+                    ConfirmationAnswer::No => ConfirmationAnswer::Yes,
+                };
+                Some(MenuSound::Focus)
+            }
+            NavigationEvent::Select => {
+                let dialog = self.dialog.take()?;
+                match dialog.focus {
+                    ConfirmationAnswer::Yes => {
+                        dialog.action.handle(&mut self.state, event_writer);
+                        Some(MenuSound::Select)
+                    }
+                    ConfirmationAnswer::No => Some(MenuSound::Back),
+                }
+            }
+            NavigationEvent::Back => {
+                self.dialog = None;
+                Some(MenuSound::Back)
+            }
         }
     }
 
@@ -145,4 +835,9 @@ where
             }
         }
     }
+
+    /// The screens on the stack, the top-most screen last
+    pub(crate) fn stack(&self) -> &[S] {
+        &self.stack
+    }
 }
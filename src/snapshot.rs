@@ -0,0 +1,265 @@
+//! Menu snapshots
+//! A deterministic description of what the menu renders: every screen on the
+//! stack with its items and which item is focused. It can be serialized or
+//! printed as a text outline in order to snapshot-test menus:
+//!
+//! ```text
+//! Screen Root "root"
+//!     Headline "Basic Example"
+//!   > Action "Close" [icon: Back]
+//!     Label "A submenu"
+//!     Screen "Boolean"
+//! Screen Booleans "boolean"
+//!     Label "Toggles some booleans"
+//!   > Action "Toggle Boolean 1" [checked]
+//!     Action "Toggle Boolean 2" [unchecked]
+//! ```
+//!
+//! An open confirmation dialog follows the screens, its focused button
+//! is marked in the same way. Localized labels are shown in the language
+//! of the `MenuLocalization`, or as their key without one.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::localization::MenuLocalization;
+use crate::navigation_menu::NavigationMenu;
+use crate::types::{ConfirmationAnswer, MenuIcon, MenuItem};
+use crate::widgets::VerticalMenu;
+use crate::{ScreenTrait, Selections};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MenuSnapshot {
+    /// The screens on the stack, the top-most screen last
+    pub screens: Vec<ScreenSnapshot>,
+    /// The open confirmation dialog
+    #[serde(default)]
+    pub dialog: Option<DialogSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreenSnapshot {
+    /// The `Debug` representation of the screen
+    pub screen: String,
+    /// The `WidgetId` of the screen's `Menu`
+    pub id: String,
+    pub items: Vec<ItemSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialogSnapshot {
+    pub text: String,
+    pub yes: String,
+    pub no: String,
+    /// Whether `Yes` or `No` is focused
+    pub yes_focused: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemKind {
+    Screen,
+    Action,
+    Slider,
+    TextInput,
+    KeyBinding,
+    Label,
+    Headline,
+    Image,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemSnapshot {
+    pub kind: ItemKind,
+    pub label: String,
+    /// The icon, unless it is the checked or unchecked icon
+    pub icon: Option<String>,
+    /// Whether the item is checked, if it has a checked or unchecked icon
+    pub checked: Option<bool>,
+    /// The value of a slider or the text of a text input
+    pub value: Option<String>,
+    pub focused: bool,
+    pub disabled: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The question of the confirmation dialog of an action
+    #[serde(default)]
+    pub confirmation: Option<String>,
+}
+
+impl MenuSnapshot {
+    pub(crate) fn new<S>(
+        menu: &NavigationMenu<S>,
+        selections: &Selections,
+        localization: Option<&MenuLocalization>,
+    ) -> Self
+    where
+        S: ScreenTrait + 'static,
+    {
+        let screens = menu
+            .stack()
+            .iter()
+            .map(|screen| {
+                let mut menu_desc = screen.resolve(&menu.state);
+                if let Some(localization) = localization {
+                    for item in menu_desc.entries.iter_mut() {
+                        item.localize(localization);
+                    }
+                }
+                let focused =
+                    VerticalMenu::focused_item(&menu_desc.id, &menu_desc.entries, selections)
+                        .map(|(index, _)| index);
+                let mut selectable_index = 0;
+                let items = menu_desc
+                    .entries
+                    .iter()
+                    .map(|item| {
+                        let is_focused = item.is_selectable() && Some(selectable_index) == focused;
+                        if item.is_selectable() {
+                            selectable_index += 1;
+                        }
+                        ItemSnapshot::new(item, is_focused)
+                    })
+                    .collect();
+                ScreenSnapshot {
+                    screen: format!("{screen:?}"),
+                    id: menu_desc.id.as_str().to_string(),
+                    items,
+                }
+            })
+            .collect();
+        let dialog = menu.dialog().map(|dialog| {
+            let mut confirmation = dialog.confirmation.clone();
+            if let Some(localization) = localization {
+                confirmation.localize(localization);
+            }
+            DialogSnapshot {
+                text: confirmation.text.debug_text().trim_end().to_string(),
+                yes: confirmation.yes.debug_text().trim_end().to_string(),
+                no: confirmation.no.debug_text().trim_end().to_string(),
+                yes_focused: dialog.focus == ConfirmationAnswer::Yes,
+            }
+        });
+        Self { screens, dialog }
+    }
+
+    /// The snapshot as a text outline. Same as the `Display` implementation.
+    pub fn outline(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ItemSnapshot {
+    fn new<S>(item: &MenuItem<S>, focused: bool) -> Self
+    where
+        S: ScreenTrait,
+    {
+        let (kind, label, icon, value) = match item.inner() {
+            MenuItem::Screen(label, icon, _) => {
+                (ItemKind::Screen, label.debug_text(), Some(icon), None)
+            }
+            MenuItem::Action(label, icon, _) => {
+                (ItemKind::Action, label.debug_text(), Some(icon), None)
+            }
+            MenuItem::Slider(label, icon, _, value) => (
+                ItemKind::Slider,
+                label.debug_text(),
+                Some(icon),
+                Some(value.display_text()),
+            ),
+            MenuItem::TextInput(label, icon, _, value) => (
+                ItemKind::TextInput,
+                label.debug_text(),
+                Some(icon),
+                Some(value.text.clone()),
+            ),
+            MenuItem::KeyBinding(label, icon, _, value) => (
+                ItemKind::KeyBinding,
+                label.debug_text(),
+                Some(icon),
+                value.input.map(|input| input.label()),
+            ),
+            MenuItem::Label(label, icon) => (ItemKind::Label, label.debug_text(), Some(icon), None),
+            MenuItem::Headline(label, icon) => {
+                (ItemKind::Headline, label.debug_text(), Some(icon), None)
+            }
+            MenuItem::Image(_, _) => (ItemKind::Image, String::new(), None, None),
+            MenuItem::Described(..) | MenuItem::Confirmed(..) | MenuItem::Disabled(..) => {
+                unreachable!("`inner` removes the description, confirmation and disabled state")
+            }
+        };
+        let (icon, checked) = match icon {
+            None | Some(MenuIcon::None) => (None, None),
+            Some(MenuIcon::Checked) => (None, Some(true)),
+            Some(MenuIcon::Unchecked) => (None, Some(false)),
+            Some(MenuIcon::Back) => (Some("Back"), None),
+            Some(MenuIcon::Controls) => (Some("Controls"), None),
+            Some(MenuIcon::Sound) => (Some("Sound"), None),
+            Some(MenuIcon::Players) => (Some("Players"), None),
+            Some(MenuIcon::Settings) => (Some("Settings"), None),
+            Some(MenuIcon::Other(_)) => (Some("Other"), None),
+        };
+        Self {
+            kind,
+            // Rich text labels have a trailing separator
+            label: label.trim_end().to_string(),
+            icon: icon.map(str::to_string),
+            checked,
+            value,
+            focused,
+            disabled: item.is_disabled(),
+            description: item
+                .description()
+                .map(|description| description.text.debug_text().trim_end().to_string()),
+            confirmation: item
+                .confirmation()
+                .map(|confirmation| confirmation.text.debug_text().trim_end().to_string()),
+        }
+    }
+}
+
+impl fmt::Display for MenuSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for screen in &self.screens {
+            writeln!(f, "Screen {} {:?}", screen.screen, screen.id)?;
+            for item in &screen.items {
+                let marker = if item.focused { ">" } else { " " };
+                write!(f, "  {marker} {:?}", item.kind)?;
+                if item.kind != ItemKind::Image {
+                    write!(f, " {:?}", item.label)?;
+                }
+                if let Some(value) = &item.value {
+                    write!(f, " = {value:?}")?;
+                }
+                if let Some(icon) = &item.icon {
+                    write!(f, " [icon: {icon}]")?;
+                }
+                match item.checked {
+                    Some(true) => write!(f, " [checked]")?,
+                    Some(false) => write!(f, " [unchecked]")?,
+                    None => (),
+                }
+                if item.disabled {
+                    write!(f, " [disabled]")?;
+                }
+                if let Some(description) = &item.description {
+                    write!(f, " [description: {description:?}]")?;
+                }
+                if let Some(confirmation) = &item.confirmation {
+                    write!(f, " [confirmation: {confirmation:?}]")?;
+                }
+                writeln!(f)?;
+            }
+        }
+        if let Some(dialog) = &self.dialog {
+            writeln!(f, "Dialog {:?}", dialog.text)?;
+            let (yes, no) = if dialog.yes_focused {
+                (">", " ")
+            } else {
+                (" ", ">")
+            };
+            writeln!(f, "  {yes} Yes {:?}", dialog.yes)?;
+            writeln!(f, "  {no} No {:?}", dialog.no)?;
+        }
+        Ok(())
+    }
+}
@@ -2,10 +2,17 @@
 //! Instead of using the bevy styles with all their properties, these simplified
 //! styles are mostly used to define the looks of menus and the different
 //! control states of buttons.
+//! A `Stylesheet` can also be loaded from a `.stylesheet.ron` or
+//! `.stylesheet.json` asset. Set the `StylesheetHandle` resource (or add it
+//! to a `QuickMenu` entity) in order to apply it to the menu, it is
+//! re-applied whenever the file changes.
 
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ControlState {
     pub fg: Color,
     pub bg: Color,
@@ -38,9 +45,17 @@ impl ControlState {
             bg: Color::WHITE,
         }
     }
+
+    fn disabled() -> Self {
+        Self {
+            fg: Color::srgb(0.5, 0.5, 0.5),
+            bg: Color::srgb(0.1, 0.1, 0.3),
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct IconStyle {
     /// The width of the icon
     pub width: Val,
@@ -63,7 +78,109 @@ impl Default for IconStyle {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SliderStyle {
+    /// The width of the track
+    pub width: Val,
+    /// The height of the track
+    pub height: Val,
+    /// The margin around the track
+    pub margin: UiRect,
+    /// The color of the empty track
+    pub track: Color,
+    /// The color of the filled part of the track
+    pub fill: Color,
+}
+
+impl Default for SliderStyle {
+    fn default() -> Self {
+        Self {
+            width: Val::Px(120.0),
+            height: Val::Px(8.0),
+            margin: UiRect::horizontal(Val::Px(10.0)),
+            track: Color::srgb(0.3, 0.3, 0.3),
+            fill: Color::srgb(1.0, 1.0, 0.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindingStyle {
+    /// The minimum width of the field that shows the bound input
+    pub width: Val,
+    /// The margin around the field
+    pub margin: UiRect,
+    /// The padding within the field
+    pub padding: UiRect,
+    /// The background of the field
+    pub background: Color,
+    /// The background of the field while waiting for an input
+    pub capturing: Color,
+    /// The background of the field if another binding uses the same input
+    pub conflict: Color,
+    /// The color of the text
+    pub text: Color,
+    /// The size of the glyph image of the bound input
+    pub glyph_size: Val,
+    /// Shown while waiting for an input
+    pub prompt: String,
+    /// Shown if nothing is bound
+    pub unbound: String,
+}
+
+impl Default for KeyBindingStyle {
+    fn default() -> Self {
+        Self {
+            width: Val::Px(120.0),
+            margin: UiRect::left(Val::Px(10.0)),
+            padding: UiRect::horizontal(Val::Px(5.0)),
+            background: Color::srgb(0.2, 0.2, 0.2),
+            capturing: Color::srgb(0.3, 0.3, 0.1),
+            conflict: Color::srgb(0.5, 0.1, 0.1),
+            text: Color::WHITE,
+            glyph_size: Val::Px(24.0),
+            prompt: "Press a key...".to_string(),
+            unbound: "-".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextInputStyle {
+    /// The minimum width of the text field
+    pub width: Val,
+    /// The margin around the text field
+    pub margin: UiRect,
+    /// The padding within the text field
+    pub padding: UiRect,
+    /// The background of the text field
+    pub background: Color,
+    /// The color of the text
+    pub text: Color,
+    /// The color of the placeholder
+    pub placeholder: Color,
+    /// Appended to the text while it is being edited
+    pub caret: String,
+}
+
+impl Default for TextInputStyle {
+    fn default() -> Self {
+        Self {
+            width: Val::Px(160.0),
+            margin: UiRect::left(Val::Px(10.0)),
+            padding: UiRect::horizontal(Val::Px(5.0)),
+            background: Color::srgb(0.2, 0.2, 0.2),
+            text: Color::WHITE,
+            placeholder: Color::srgb(0.5, 0.5, 0.5),
+            caret: "|".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StyleEntry {
     pub size: f32,
     pub margin: UiRect,
@@ -71,6 +188,9 @@ pub struct StyleEntry {
     pub normal: ControlState,
     pub hover: ControlState,
     pub selected: ControlState,
+    /// Used for disabled items, regardless of focus or hover
+    pub disabled: ControlState,
+    #[serde(default)]
     pub icon_style: IconStyle,
 }
 
@@ -83,6 +203,7 @@ impl StyleEntry {
             normal: ControlState::normal(),
             hover: ControlState::hover(),
             selected: ControlState::selected(),
+            disabled: ControlState::disabled(),
             icon_style: IconStyle::default(),
         }
     }
@@ -96,6 +217,21 @@ impl StyleEntry {
             normal: ControlState::clear(gray),
             hover: ControlState::clear(gray),
             selected: ControlState::clear(gray),
+            disabled: ControlState::clear(gray),
+            icon_style: IconStyle::default(),
+        }
+    }
+
+    pub fn breadcrumb() -> Self {
+        let gray = Color::srgb(0.7, 0.7, 0.7);
+        Self {
+            size: 18.0,
+            margin: UiRect::all(Val::Px(2.0)),
+            padding: UiRect::all(Val::Px(3.0)),
+            normal: ControlState::clear(gray),
+            hover: ControlState::clear(Color::srgb(1.0, 1.0, 0.0)),
+            selected: ControlState::clear(Color::WHITE),
+            disabled: ControlState::clear(gray),
             icon_style: IconStyle::default(),
         }
     }
@@ -108,16 +244,226 @@ impl StyleEntry {
             normal: ControlState::clear(Color::WHITE),
             hover: ControlState::clear(Color::WHITE),
             selected: ControlState::clear(Color::WHITE),
+            disabled: ControlState::clear(Color::WHITE),
             icon_style: IconStyle::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, Resource)]
+/// The texts shown above and below a scrolling menu if there are
+/// more items in that direction. They use the `label` style.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrollIndicators {
+    pub up: String,
+    pub down: String,
+}
+
+impl Default for ScrollIndicators {
+    fn default() -> Self {
+        Self {
+            up: "↑".to_string(),
+            down: "↓".to_string(),
+        }
+    }
+}
+
+/// How the screens on the stack are laid out
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum MenuLayout {
+    /// All screens side by side in a row ("miller columns")
+    #[default]
+    Columns,
+    /// Only the top-most screen, e.g. for portrait or small screens
+    SingleScreen(SingleScreenLayout),
+}
+
+/// The header above the screen in the `SingleScreen` layout
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SingleScreenLayout {
+    /// The text of a button that goes back. It uses the `button` style
+    /// and is not shown on the root screen.
+    pub back_button: Option<String>,
+}
+
+/// The bar above the screens with the titles of the stacked screens.
+/// Screens without a title (see `Menu::with_title`) are skipped.
+/// Clicking a title goes back to that screen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Breadcrumb {
+    /// `normal` is used for the previous screens, `hover` while the mouse
+    /// is over them and `selected` for the current screen
+    pub style: StyleEntry,
+    pub separator: String,
+}
+
+impl Default for Breadcrumb {
+    fn default() -> Self {
+        Self {
+            style: StyleEntry::breadcrumb(),
+            separator: "›".to_string(),
+        }
+    }
+}
+
+/// Where the description panel is placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DescriptionPosition {
+    /// A bar below the screens
+    #[default]
+    Bottom,
+    /// A column to the right of the screens
+    Side,
+}
+
+/// The panel with the description of the focused item of the top-most
+/// screen (see `MenuItem::with_description`). It stays in place while
+/// items without a description are focused.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DescriptionPanel {
+    pub position: DescriptionPosition,
+    /// The height of a bottom bar or the width of a side panel
+    pub size: Val,
+    pub margin: UiRect,
+    pub padding: UiRect,
+    pub background: Color,
+    pub text: Color,
+    pub font_size: f32,
+    /// The size of the image of the description
+    pub image_size: Val,
+}
+
+impl Default for DescriptionPanel {
+    fn default() -> Self {
+        Self {
+            position: DescriptionPosition::Bottom,
+            size: Val::Px(80.0),
+            margin: UiRect::top(Val::Px(10.0)),
+            padding: UiRect::all(Val::Px(10.0)),
+            background: Color::srgb(0.1, 0.1, 0.3),
+            text: Color::srgb(0.8, 0.8, 0.8),
+            font_size: 18.0,
+            image_size: Val::Px(64.0),
+        }
+    }
+}
+
+/// Shows the description of an item next to it while the mouse is over it.
+/// Only used if there is no description panel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tooltip {
+    pub max_width: Val,
+    pub padding: UiRect,
+    pub background: Color,
+    pub text: Color,
+    pub font_size: f32,
+    /// The size of the image of the description
+    pub image_size: Val,
+}
+
+impl Default for Tooltip {
+    fn default() -> Self {
+        Self {
+            max_width: Val::Px(240.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            background: Color::srgb(0.2, 0.2, 0.2),
+            text: Color::WHITE,
+            font_size: 16.0,
+            image_size: Val::Px(48.0),
+        }
+    }
+}
+
+/// The confirmation dialog (see `MenuItem::with_confirmation`). Its buttons
+/// use the `button` style.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DialogStyle {
+    /// Covers the menu behind the dialog
+    pub overlay: Color,
+    pub background: Color,
+    pub padding: UiRect,
+    /// The color of the question
+    pub text: Color,
+    pub font_size: f32,
+}
+
+impl Default for DialogStyle {
+    fn default() -> Self {
+        Self {
+            overlay: Color::srgba(0.0, 0.0, 0.0, 0.6),
+            background: Color::srgb(0.1, 0.1, 0.3),
+            padding: UiRect::all(Val::Px(20.0)),
+            text: Color::WHITE,
+            font_size: 22.0,
+        }
+    }
+}
+
+/// How vertical menus enter and leave the menu row when screens are
+/// pushed or popped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScreenTransition {
+    /// Menus appear and disappear instantly
+    #[default]
+    None,
+    /// Menus slide in from the right and slide out to the right
+    Slide,
+    Fade,
+    Scale,
+}
+
+/// Animations of the menu. Durations are in seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Transitions {
+    pub screen: ScreenTransition,
+    pub screen_duration: f32,
+    /// The distance in pixels a sliding menu travels
+    pub slide_distance: f32,
+    /// How long the focus highlight takes to move from the previously
+    /// focused item to the newly focused one. `0` disables the animation.
+    pub focus_duration: f32,
+}
+
+impl Default for Transitions {
+    fn default() -> Self {
+        Self {
+            screen: ScreenTransition::None,
+            screen_duration: 0.2,
+            slide_distance: 40.0,
+            focus_duration: 0.0,
+        }
+    }
+}
+
+impl Transitions {
+    pub(crate) fn animates_screens(&self) -> bool {
+        self.screen != ScreenTransition::None && self.screen_duration > 0.0
+    }
+}
+
+/// Missing fields of a loaded stylesheet fall back to the default stylesheet
+#[derive(Debug, Clone, PartialEq, Resource, Asset, TypePath, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Stylesheet {
     pub button: StyleEntry,
     pub label: StyleEntry,
     pub headline: StyleEntry,
+    pub slider: SliderStyle,
+    pub text_input: TextInputStyle,
+    pub key_binding: KeyBindingStyle,
+    pub dialog: DialogStyle,
+    pub scroll_indicators: Option<ScrollIndicators>,
+    pub transitions: Transitions,
+    pub layout: MenuLayout,
+    pub breadcrumb: Option<Breadcrumb>,
+    pub description: Option<DescriptionPanel>,
+    pub tooltip: Option<Tooltip>,
     pub vertical_spacing: f32,
     pub style: Option<Style>,
     pub background: Option<BackgroundColor>,
@@ -129,6 +475,16 @@ impl Default for Stylesheet {
             button: StyleEntry::button(),
             label: StyleEntry::label(),
             headline: StyleEntry::headline(),
+            slider: SliderStyle::default(),
+            text_input: TextInputStyle::default(),
+            key_binding: KeyBindingStyle::default(),
+            dialog: DialogStyle::default(),
+            scroll_indicators: Some(ScrollIndicators::default()),
+            transitions: Transitions::default(),
+            layout: MenuLayout::default(),
+            breadcrumb: None,
+            description: None,
+            tooltip: Some(Tooltip::default()),
             vertical_spacing: 10.0,
             style: None,
             background: None,
@@ -146,4 +502,74 @@ impl Stylesheet {
         self.style = Some(style);
         self
     }
+
+    pub fn with_transitions(mut self, transitions: Transitions) -> Self {
+        self.transitions = transitions;
+        self
+    }
+
+    pub fn with_layout(mut self, layout: MenuLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn with_breadcrumb(mut self, breadcrumb: Breadcrumb) -> Self {
+        self.breadcrumb = Some(breadcrumb);
+        self
+    }
+
+    pub fn with_description(mut self, description: DescriptionPanel) -> Self {
+        self.description = Some(description);
+        self
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StylesheetLoaderError {
+    #[error("Could not read the stylesheet: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse the stylesheet: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("Could not parse the stylesheet: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Loads `Stylesheet`s from `.stylesheet.ron` and `.stylesheet.json` files
+#[derive(Default)]
+pub struct StylesheetLoader;
+
+impl AssetLoader for StylesheetLoader {
+    type Asset = Stylesheet;
+    type Settings = ();
+    type Error = StylesheetLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let is_json = load_context
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "json");
+        if is_json {
+            Ok(serde_json::from_slice(&bytes)?)
+        } else {
+            Ok(ron::de::from_bytes(&bytes)?)
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["stylesheet.ron", "stylesheet.json"]
+    }
 }
+
+/// The stylesheet asset that is applied to the menu whenever it is loaded
+/// or changes on disk. As a resource, it replaces the stylesheet given to
+/// `MenuState::new`. As a component next to a `QuickMenu`, it replaces the
+/// stylesheet of that instance.
+#[derive(Resource, Component)]
+pub struct StylesheetHandle(pub Handle<Stylesheet>);
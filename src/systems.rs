@@ -1,114 +1,748 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use bevy::window::PrimaryWindow;
 
 use crate::{
-    types::{self, ButtonComponent, CleanUpUI, MenuAssets, NavigationEvent, QuickMenuComponent},
-    ActionTrait, MenuState, RedrawEvent, ScreenTrait, Selections,
+    input::{BoundInput, InputDevice, InputRepeat, InputSource, MenuInputMap},
+    localization::MenuLocalization,
+    navigation_menu::{NavigationMenu, RenderedTree},
+    style::{ScreenTransition, Stylesheet, StylesheetHandle},
+    types::{
+        self, BackButtonComponent, BindingCapture, BindingConflict, BreadcrumbComponent,
+        ButtonComponent, CapturingBinding, CleanUpUI, DeviceNavigationEvent, DialogButtonComponent,
+        EditingTextInput, FadeOrigin, FocusChanged, FocusTransition, MenuAssets, MenuClosed,
+        MenuFocus, MenuId, MenuOpened, MenuOwner, MenuResources, MenuSelection, MenuSound,
+        MenuTransition, MenuWarning, MirroredNavigation, NavigationEvent, RenderedDialog,
+        ScreenPopped, ScreenPushed, ScrollOffsets, SliderComponent, TextEditing, TooltipAnchor,
+        TooltipComponent, VerticalMenuComponent, WidgetId,
+    },
+    ActionTrait, MenuItem, MenuState, QuickMenu, RedrawEvent, ScreenTrait, Selections,
 };
 
+/// The parts of a menu that the systems work with. They either come from the
+/// `MenuState` resource and the `MenuResources` of its screen type, or from
+/// a `QuickMenu` instance.
+pub struct MenuContext<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub menu_state: &'a mut MenuState<S>,
+    pub selections: &'a mut Selections,
+    pub scrolls: &'a mut ScrollOffsets,
+    pub editing: &'a mut TextEditing,
+    pub capture: &'a mut BindingCapture,
+    pub input_enabled: bool,
+    /// The device this menu reacts to, `None` for any device
+    pub input_device: Option<InputDevice>,
+}
+
+/// All menus of a screen type: the `MenuState` resource, if it exists,
+/// and the `QuickMenu` instances. Menus are identified by their owner,
+/// `None` for the resource and the entity for an instance.
+#[derive(SystemParam)]
+pub struct Menus<'w, 's, S>
+where
+    S: ScreenTrait + 'static,
+{
+    menu_state: Option<ResMut<'w, MenuState<S>>>,
+    resources: ResMut<'w, MenuResources<S>>,
+    instances: Query<'w, 's, (Entity, &'static mut QuickMenu<S>)>,
+    parents: Query<'w, 's, &'static Parent>,
+    owners: Query<'w, 's, &'static MenuOwner<S>>,
+}
+
+impl<'w, 's, S> Menus<'w, 's, S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub fn for_each(&mut self, mut f: impl FnMut(Option<Entity>, MenuContext<'_, S>)) {
+        self.for_each_with_assets(|owner, context, _| f(owner, context));
+    }
+
+    /// Like `for_each`, along with the assets of the screen type
+    pub fn for_each_with_assets(
+        &mut self,
+        mut f: impl FnMut(Option<Entity>, MenuContext<'_, S>, &MenuAssets),
+    ) {
+        let MenuResources {
+            assets,
+            selections,
+            scrolls,
+            editing,
+            capture,
+            ..
+        } = &mut *self.resources;
+        if let Some(menu_state) = self.menu_state.as_deref_mut() {
+            f(
+                None,
+                MenuContext {
+                    menu_state,
+                    selections,
+                    scrolls,
+                    editing,
+                    capture,
+                    input_enabled: true,
+                    input_device: None,
+                },
+                assets,
+            );
+        }
+        for (entity, mut instance) in &mut self.instances {
+            f(Some(entity), instance.context(), assets);
+        }
+    }
+
+    pub fn get(&mut self, owner: Option<Entity>) -> Option<MenuContext<'_, S>> {
+        match owner {
+            None => {
+                let MenuResources {
+                    selections,
+                    scrolls,
+                    editing,
+                    capture,
+                    ..
+                } = &mut *self.resources;
+                self.menu_state
+                    .as_deref_mut()
+                    .map(|menu_state| MenuContext {
+                        menu_state,
+                        selections,
+                        scrolls,
+                        editing,
+                        capture,
+                        input_enabled: true,
+                        input_device: None,
+                    })
+            }
+            Some(entity) => self
+                .instances
+                .get_mut(entity)
+                .ok()
+                .map(|(_, instance)| instance.into_inner().context()),
+        }
+    }
+
+    /// The owner of the menu that a rendered entity belongs to
+    pub fn owner_of(&self, entity: Entity) -> Option<Option<Entity>> {
+        std::iter::once(entity)
+            .chain(self.parents.iter_ancestors(entity))
+            .find_map(|ancestor| self.owners.get(ancestor).ok())
+            .map(|owner| owner.owner)
+    }
+}
+
+/// Whether there is a menu of this screen type, either the `MenuState`
+/// resource or a `QuickMenu` instance
+pub fn menu_exists<S>(
+    menu_state: Option<Res<MenuState<S>>>,
+    instances: Query<(), With<QuickMenu<S>>>,
+) -> bool
+where
+    S: ScreenTrait + 'static,
+{
+    menu_state.is_some() || !instances.is_empty()
+}
+
+/// The writers for the events about changes of the focus, and the sounds
+/// that go along with the changes of the screens and the focus
+#[derive(SystemParam)]
+pub struct LifecycleWriters<'w, S>
+where
+    S: ScreenTrait + 'static,
+{
+    focus: EventWriter<'w, FocusChanged<S>>,
+    sound: EventWriter<'w, MenuSound>,
+}
+
+/// The stack and focus before an input is applied. Afterwards, the focus
+/// change is sent as a lifecycle event. The changes of the stack are sent
+/// by `redraw_system`, which also sees the changes that were made via
+/// the `MenuState`.
+struct Lifecycle<S>
+where
+    S: ScreenTrait + 'static,
+{
+    stack: Vec<S>,
+    focus: MenuSelection<S>,
+}
+
+impl<S> Lifecycle<S>
+where
+    S: ScreenTrait + 'static,
+{
+    fn capture(menu: &NavigationMenu<S>, selections: &Selections) -> Self {
+        Self {
+            stack: menu.stack().to_vec(),
+            focus: Self::focus(menu, selections),
+        }
+    }
+
+    fn focus(menu: &NavigationMenu<S>, selections: &Selections) -> MenuSelection<S> {
+        menu.focused_item(selections)
+            .map(|item| item.as_selection())
+            .unwrap_or(MenuSelection::None)
+    }
+
+    /// Send the events for the changes. `selected` is set if an item was
+    /// selected, in order to pick the right sound.
+    fn emit(
+        self,
+        menu: &NavigationMenu<S>,
+        selections: &Selections,
+        writers: &mut LifecycleWriters<S>,
+        selected: bool,
+    ) {
+        let stack = menu.stack();
+        let common = self
+            .stack
+            .iter()
+            .zip(stack)
+            .take_while(|(old, new)| old == new)
+            .count();
+        let focus = Self::focus(menu, selections);
+        let focus_changed = focus != self.focus;
+        if focus_changed {
+            writers.focus.send(FocusChanged {
+                old: self.focus,
+                new: focus,
+            });
+        }
+        if selected || common < stack.len() {
+            writers.sound.send(MenuSound::Select);
+        } else if common < self.stack.len() {
+            writers.sound.send(MenuSound::Back);
+        } else if focus_changed {
+            writers.sound.send(MenuSound::Focus);
+        }
+    }
+}
+
+/// Maps the inputs from the `MenuInputMap` onto `DeviceNavigationEvent`s,
+/// repeating held inputs as configured in `RepeatSettings`
+#[allow(clippy::too_many_arguments)]
 pub fn keyboard_input_system(
+    input_map: Res<MenuInputMap>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut writer: EventWriter<NavigationEvent>,
+    mut writer: EventWriter<DeviceNavigationEvent>,
+    mut navigation: ResMut<Events<NavigationEvent>>,
+    mut mirrored: ResMut<MirroredNavigation>,
     gamepads: Res<Gamepads>,
     button_inputs: Res<ButtonInput<GamepadButton>>,
     axes: Res<Axis<GamepadAxis>>,
+    time: Res<Time>,
+    editing_inputs: Query<(), With<EditingTextInput>>,
+    capturing_bindings: Query<(), With<CapturingBinding>>,
+    focus: Res<MenuFocus>,
+    mut repeat: Local<InputRepeat>,
 ) {
-    use NavigationEvent::*;
-    if keyboard_input.just_pressed(KeyCode::ArrowDown) {
-        writer.send(Down);
-    } else if keyboard_input.just_pressed(KeyCode::ArrowUp) {
-        writer.send(Up);
-    } else if keyboard_input.just_pressed(KeyCode::Enter) {
-        writer.send(Select);
-    } else if keyboard_input.just_pressed(KeyCode::Backspace) {
-        writer.send(Back);
+    // Inputs that are held while no menu is open don't repeat into the
+    // next menu
+    if focus.is_empty() {
+        *repeat = InputRepeat::default();
+        return;
+    }
+    let settings = &input_map.repeat;
+    let delta = time.delta_seconds();
+    let mut events = Vec::new();
+
+    // While a key binding waits for an input, nothing navigates
+    if !capturing_bindings.is_empty() {
+        repeat.finish_frame();
+        return;
+    }
+
+    // Only one keyboard event per frame, the first binding wins.
+    // While a text input is edited, the keyboard is used for typing.
+    let keys = if !editing_inputs.is_empty() {
+        &[][..]
+    } else {
+        &input_map.keys[..]
+    };
+    let mut key_event = None;
+    for (key, event) in keys.iter() {
+        let source = InputSource::Key(*key);
+        let pressed = keyboard_input.pressed(*key);
+        let just_pressed = keyboard_input.just_pressed(*key);
+        if repeat.update(source, pressed, just_pressed, *event, settings, delta) {
+            key_event = key_event.or(Some(*event));
+        }
+    }
+    if let Some(event) = key_event {
+        events.push(DeviceNavigationEvent {
+            event,
+            device: InputDevice::Keyboard,
+        });
     }
 
     for gamepad in gamepads.iter() {
-        if button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadDown)) {
-            writer.send(Down);
-        } else if button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadUp))
-        {
-            writer.send(Up);
-        } else if button_inputs
-            .just_pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadRight))
-        {
-            writer.send(Back);
-        } else if button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::South))
-            || button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::West))
-        {
-            writer.send(Select);
-        } else if button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::East))
-            || button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::North))
-        {
-            writer.send(Back);
-        }
-        if axes.is_changed() {
-            for (axis, check_negative, action) in [
-                (GamepadAxisType::LeftStickX, true, Back),
-                (GamepadAxisType::LeftStickY, true, Down),
-                (GamepadAxisType::LeftStickY, false, Up),
-                (GamepadAxisType::RightStickX, true, Back),
-                (GamepadAxisType::RightStickY, true, Down),
-                (GamepadAxisType::RightStickY, false, Up),
-            ] {
-                if let Some(value) = axes.get(GamepadAxis::new(gamepad, axis)) {
-                    if (check_negative && value < -0.1) || (!check_negative && value > 0.1) {
-                        writer.send(action);
-                    }
-                }
+        let device = InputDevice::Gamepad(gamepad);
+        let mut button_event = None;
+        for (button_type, event) in input_map.gamepad_buttons.iter() {
+            let button = GamepadButton::new(gamepad, *button_type);
+            let source = InputSource::Button(button);
+            let pressed = button_inputs.pressed(button);
+            let just_pressed = button_inputs.just_pressed(button);
+            if repeat.update(source, pressed, just_pressed, *event, settings, delta) {
+                button_event = button_event.or(Some(*event));
             }
         }
+        if let Some(event) = button_event {
+            events.push(DeviceNavigationEvent { event, device });
+        }
+
+        for (index, binding) in input_map.axes.iter().enumerate() {
+            let source = InputSource::Axis(gamepad, index);
+            let value = axes
+                .get(GamepadAxis::new(gamepad, binding.axis))
+                .unwrap_or_default();
+            let active = binding.is_active(value, settings, repeat.is_held(&source));
+            let just_pressed = repeat.just_activated(&source, active);
+            if repeat.update(source, active, just_pressed, binding.event, settings, delta) {
+                events.push(DeviceNavigationEvent {
+                    event: binding.event,
+                    device,
+                });
+            }
+        }
+    }
+
+    repeat.finish_frame();
+    // Readers of `NavigationEvent` see the built-in input as well
+    let oldest = navigation.oldest_event_count();
+    mirrored.0.retain(|id| id.id >= oldest);
+    for event in events {
+        mirrored.0.push(navigation.send(event.event));
+        writer.send(event);
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn redraw_system<S>(
     mut commands: Commands,
-    existing: Query<Entity, With<QuickMenuComponent>>,
-    mut menu_state: ResMut<MenuState<S>>,
-    selections: Res<Selections>,
+    tree: RenderedTree<S>,
+    mut menus: Menus<S>,
     redraw_reader: EventReader<RedrawEvent>,
-    assets: Res<MenuAssets>,
-    // mut initial_render_done: Local<bool>,
+    mut warning_writer: EventWriter<MenuWarning>,
+    mut opened_writer: EventWriter<MenuOpened>,
+    mut pushed_writer: EventWriter<ScreenPushed<S>>,
+    mut popped_writer: EventWriter<ScreenPopped<S>>,
+    localization: Option<Res<MenuLocalization>>,
 ) where
     S: ScreenTrait + 'static,
 {
-    let mut can_redraw = !redraw_reader.is_empty();
-    if !menu_state.initial_render_done {
-        menu_state.initial_render_done = true;
-        can_redraw = true;
+    // Switching the language redraws everything
+    let redraw_all = !redraw_reader.is_empty()
+        || localization
+            .as_ref()
+            .is_some_and(|localization| localization.is_changed());
+    menus.for_each_with_assets(|owner, context, assets| {
+        let MenuContext {
+            menu_state,
+            selections,
+            scrolls,
+            editing,
+            capture,
+            ..
+        } = context;
+        let stack = menu_state.menu.stack();
+        if menu_state.reported_stack != stack {
+            let common = menu_state
+                .reported_stack
+                .iter()
+                .zip(stack)
+                .take_while(|(old, new)| old == new)
+                .count();
+            for screen in menu_state.reported_stack[common..].iter().rev() {
+                popped_writer.send(ScreenPopped(*screen));
+            }
+            for screen in &stack[common..] {
+                pushed_writer.send(ScreenPushed(*screen));
+            }
+            menu_state.reported_stack = stack.to_vec();
+        }
+        let mut can_redraw = redraw_all;
+        if !menu_state.initial_render_done {
+            menu_state.initial_render_done = true;
+            can_redraw = true;
+            opened_writer.send(MenuOpened);
+        }
+        if menu_state.redraw_requested {
+            menu_state.redraw_requested = false;
+            can_redraw = true;
+        }
+        if can_redraw {
+            warning_writer.send_batch(menu_state.menu.clamp_selections(selections));
+            warning_writer.send_batch(menu_state.menu.empty_screen_warning());
+            menu_state.menu.show(
+                owner,
+                assets,
+                selections,
+                scrolls,
+                editing,
+                capture,
+                localization.as_deref(),
+                &tree,
+                &mut commands,
+            );
+        }
+    });
+}
+
+/// Apply the `StylesheetHandle`s once they are loaded and whenever they change
+/// on disk. The resource applies to the `MenuState`, a component to the
+/// `QuickMenu` on the same entity.
+pub fn stylesheet_system<S>(
+    mut asset_events: EventReader<AssetEvent<Stylesheet>>,
+    stylesheets: Res<Assets<Stylesheet>>,
+    handle: Option<Res<StylesheetHandle>>,
+    menu_state: Option<ResMut<MenuState<S>>>,
+    mut instances: Query<(Ref<StylesheetHandle>, &mut QuickMenu<S>)>,
+) where
+    S: ScreenTrait + 'static,
+{
+    let changed: Vec<AssetId<Stylesheet>> = asset_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    let apply = |menu_state: &mut MenuState<S>, id: AssetId<Stylesheet>| {
+        let Some(sheet) = stylesheets.get(id) else {
+            return;
+        };
+        if menu_state.stylesheet() != sheet {
+            menu_state.set_stylesheet(sheet.clone());
+        }
+    };
+    // Also apply if the menu or the handle were (re)inserted after loading
+    if let (Some(handle), Some(mut menu_state)) = (handle, menu_state) {
+        let id = handle.0.id();
+        if changed.contains(&id) || handle.is_changed() || menu_state.is_added() {
+            apply(&mut menu_state, id);
+        }
+    }
+    for (handle, mut instance) in &mut instances {
+        let id = handle.0.id();
+        if changed.contains(&id) || handle.is_changed() || instance.is_added() {
+            apply(instance.menu_state_mut(), id);
+        }
+    }
+}
+
+/// Apply a navigation event to a menu
+fn navigate<S>(
+    context: &mut MenuContext<S>,
+    event: &NavigationEvent,
+    event_writer: &mut EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    warning_writer: &mut EventWriter<MenuWarning>,
+    lifecycle_writers: &mut LifecycleWriters<S>,
+) where
+    S: ScreenTrait + 'static,
+{
+    let MenuContext {
+        menu_state,
+        selections,
+        editing,
+        capture,
+        ..
+    } = context;
+    // An open confirmation dialog traps the focus
+    if menu_state.menu.confirming().is_some() {
+        menu_state.redraw_requested = true;
+        if let Some(sound) = menu_state.menu.apply_dialog_event(event, event_writer) {
+            lifecycle_writers.sound.send(sound);
+        }
+        return;
     }
-    if can_redraw {
-        for item in existing.iter() {
-            commands.entity(item).despawn_recursive();
+    warning_writer.send_batch(menu_state.menu.clamp_selections(selections));
+    menu_state.redraw_requested = true;
+    if let Some(adjustment) = event.adjustment() {
+        if menu_state
+            .menu
+            .apply_adjustment(adjustment, selections, event_writer)
+        {
+            lifecycle_writers.sound.send(MenuSound::ValueChange);
+            return;
         }
-        menu_state.menu.show(&assets, &selections, &mut commands);
     }
+    let lifecycle = Lifecycle::capture(&menu_state.menu, selections);
+    let mut selected = false;
+    if event == &NavigationEvent::Select {
+        if let Some((text_input, text)) = menu_state.menu.focused_text_input(selections) {
+            editing.start(text_input, text);
+            selected = true;
+        }
+        if let Some(binding) = menu_state.menu.focused_key_binding(selections) {
+            capture.start(binding);
+            selected = true;
+        }
+        let disabled = match menu_state.menu.focused_item(selections) {
+            Some(item) => item.is_disabled(),
+            None => true,
+        };
+        if disabled {
+            lifecycle_writers.sound.send(MenuSound::Error);
+        }
+    }
+    if let Some(selection) = menu_state.menu.apply_event(event, selections) {
+        menu_state.menu.handle_selection(&selection, event_writer);
+        selected = true;
+    }
+    lifecycle.emit(&menu_state.menu, selections, lifecycle_writers, selected);
 }
 
+/// Keep track of the open menus of this screen type in the `MenuFocus`.
+/// A menu gets the focus when it opens.
+pub fn menu_focus_system<S>(
+    mut focus: ResMut<MenuFocus>,
+    menu_state: Option<Res<MenuState<S>>>,
+    opened: Query<Entity, Added<QuickMenu<S>>>,
+    mut closed: RemovedComponents<QuickMenu<S>>,
+) where
+    S: ScreenTrait + 'static,
+{
+    match menu_state {
+        Some(menu_state) if menu_state.is_added() => focus.open(MenuId::resource::<S>()),
+        Some(_) => (),
+        None => focus.close(MenuId::resource::<S>()),
+    }
+    for entity in closed.read() {
+        focus.close(MenuId::Instance(entity));
+    }
+    for entity in &opened {
+        focus.open(MenuId::Instance(entity));
+    }
+}
+
+/// Apply the next navigation event to the focused menu, if it accepts
+/// input. A `DeviceNavigationEvent` also reaches the menus of its device,
+/// so that each player can control their own menu.
+#[allow(clippy::too_many_arguments)]
 pub fn input_system<S>(
     mut reader: EventReader<NavigationEvent>,
-    mut menu_state: ResMut<MenuState<S>>,
-    mut redraw_writer: EventWriter<RedrawEvent>,
-    mut selections: ResMut<Selections>,
+    mut device_reader: EventReader<DeviceNavigationEvent>,
+    mirrored: Res<MirroredNavigation>,
+    focus: Res<MenuFocus>,
+    mut menus: Menus<S>,
     mut event_writer: EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut warning_writer: EventWriter<MenuWarning>,
+    mut lifecycle_writers: LifecycleWriters<S>,
 ) where
     S: ScreenTrait + 'static,
 {
-    if let Some(event) = reader.read().next() {
-        if let Some(selection) = menu_state.menu.apply_event(event, &mut selections) {
-            menu_state
-                .menu
-                .handle_selection(&selection, &mut event_writer);
+    let events: Vec<(NavigationEvent, Option<InputDevice>)> = reader
+        .read_with_id()
+        .filter(|(_, id)| !mirrored.0.contains(id))
+        .map(|(event, _)| (*event, None))
+        .chain(
+            device_reader
+                .read()
+                .map(|event| (event.event, Some(event.device))),
+        )
+        .collect();
+    if events.is_empty() {
+        return;
+    }
+    let focused = focus.focused();
+    menus.for_each(|owner, mut context| {
+        if !context.input_enabled {
+            return;
+        }
+        let has_focus = focused == Some(MenuId::of::<S>(owner));
+        // A device always controls its own menus, everything else only
+        // reaches the focused menu
+        let accepts = |device: &Option<InputDevice>| match (device, context.input_device) {
+            (Some(device), Some(own)) => *device == own,
+            _ => has_focus,
+        };
+        if let Some((event, _)) = events.iter().find(|(_, device)| accepts(device)) {
+            navigate(
+                &mut context,
+                event,
+                &mut event_writer,
+                &mut warning_writer,
+                &mut lifecycle_writers,
+            );
+        }
+    });
+}
+
+/// Feed typed characters into the `MenuItem::TextInput` that is being edited
+pub fn text_input_system<S>(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut menus: Menus<S>,
+    mut event_writer: EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+) where
+    S: ScreenTrait + 'static,
+{
+    let keys: Vec<Key> = keyboard_events
+        .read()
+        .filter(|event| event.state.is_pressed())
+        .map(|event| event.logical_key.clone())
+        .collect();
+    menus.for_each(|_, context| {
+        let MenuContext {
+            menu_state,
+            selections,
+            editing,
+            ..
+        } = context;
+        let Some(target) = editing.target.as_ref() else {
+            return;
+        };
+        // Stop editing once the focus moved somewhere else
+        let focused = menu_state.menu.focused_text_input(selections);
+        if focused.as_ref().map(|(text_input, _)| text_input) != Some(target) {
+            editing.target = None;
+            menu_state.redraw_requested = true;
+            return;
+        }
+        if keys.is_empty() {
+            return;
+        }
+        if menu_state
+            .menu
+            .apply_text_input(&keys, &editing.original, selections, &mut event_writer)
+        {
+            editing.target = None;
+        }
+        menu_state.redraw_requested = true;
+    });
+}
+
+/// Bind the next key, mouse button or gamepad input to the
+/// `MenuItem::KeyBinding` that is capturing. `Escape` and the timeout
+/// of the key binding cancel the capture.
+#[allow(clippy::too_many_arguments)]
+pub fn binding_capture_system<S>(
+    mut menus: Menus<S>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    time: Res<Time>,
+    mut active_axes: Local<Vec<(Gamepad, GamepadAxisType, bool)>>,
+    mut event_writer: EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut conflict_writer: EventWriter<BindingConflict<S>>,
+    mut sound_writer: EventWriter<MenuSound>,
+) where
+    S: ScreenTrait + 'static,
+{
+    use GamepadAxisType::*;
+    let cancel = keys.just_pressed(KeyCode::Escape);
+
+    // Axes only count when they are pushed, not while they are held
+    let mut inputs: Vec<(BoundInput, InputDevice)> = Vec::new();
+    let mut axes_now = Vec::new();
+    for gamepad in gamepads.iter() {
+        for axis in [
+            LeftStickX,
+            LeftStickY,
+            RightStickX,
+            RightStickY,
+            LeftZ,
+            RightZ,
+        ] {
+            let value = axes
+                .get(GamepadAxis::new(gamepad, axis))
+                .unwrap_or_default();
+            if value.abs() > 0.5 {
+                axes_now.push((gamepad, axis, value > 0.0));
+            }
         }
-        redraw_writer.send(RedrawEvent);
     }
+    inputs.extend(
+        keys.get_just_pressed()
+            .map(|key| (BoundInput::Key(*key), InputDevice::Keyboard)),
+    );
+    inputs.extend(
+        mouse_buttons
+            .get_just_pressed()
+            .map(|button| (BoundInput::Mouse(*button), InputDevice::Keyboard)),
+    );
+    inputs.extend(gamepad_buttons.get_just_pressed().map(|button| {
+        (
+            BoundInput::GamepadButton(button.button_type),
+            InputDevice::Gamepad(button.gamepad),
+        )
+    }));
+    inputs.extend(
+        axes_now
+            .iter()
+            .filter(|axis| !active_axes.contains(axis))
+            .map(|(gamepad, axis, positive)| {
+                (
+                    BoundInput::GamepadAxis(*axis, *positive),
+                    InputDevice::Gamepad(*gamepad),
+                )
+            }),
+    );
+    *active_axes = axes_now;
+
+    menus.for_each(|_, context| {
+        let MenuContext {
+            menu_state,
+            selections,
+            capture,
+            input_device,
+            ..
+        } = context;
+        capture.captured = false;
+        let Some(target) = capture.target.as_ref() else {
+            return;
+        };
+        // Stop capturing once the focus moved somewhere else
+        if menu_state.menu.focused_key_binding(selections).as_ref() != Some(target) {
+            capture.target = None;
+            menu_state.redraw_requested = true;
+            return;
+        }
+        let Some(MenuItem::KeyBinding(_, _, _, value)) = menu_state.menu.focused_item(selections)
+        else {
+            return;
+        };
+        capture.elapsed += time.delta_seconds();
+        if cancel || capture.elapsed > value.timeout {
+            capture.target = None;
+            menu_state.redraw_requested = true;
+            sound_writer.send(MenuSound::Back);
+            return;
+        }
+        let Some((input, _)) = inputs
+            .iter()
+            .find(|(_, device)| input_device.is_none() || input_device == Some(*device))
+        else {
+            return;
+        };
+        capture.target = None;
+        capture.captured = true;
+        menu_state.redraw_requested = true;
+        match menu_state
+            .menu
+            .apply_binding(*input, selections, &mut event_writer)
+        {
+            Some(conflict) => {
+                conflict_writer.send(conflict);
+                sound_writer.send(MenuSound::Error);
+            }
+            None => {
+                sound_writer.send(MenuSound::ValueChange);
+            }
+        }
+    });
 }
 
 #[allow(clippy::type_complexity)]
 pub fn mouse_system<S>(
-    mut menu_state: ResMut<MenuState<S>>,
+    mut menus: Menus<S>,
     mut interaction_query: Query<
         (
+            Entity,
             &Interaction,
             &types::ButtonComponent<S>,
             &mut BackgroundColor,
@@ -116,24 +750,50 @@ pub fn mouse_system<S>(
         Changed<Interaction>,
     >,
     mut event_writer: EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
-    mut selections: ResMut<Selections>,
-    mut redraw_writer: EventWriter<RedrawEvent>,
+    mut lifecycle_writers: LifecycleWriters<S>,
+    mut focus: ResMut<MenuFocus>,
 ) where
     S: ScreenTrait + 'static,
 {
     for (
+        entity,
         interaction,
         ButtonComponent {
             selection,
             style,
             menu_identifier,
             selected,
+            disabled,
         },
         mut background_color,
     ) in &mut interaction_query
     {
+        if *disabled {
+            continue;
+        }
         match *interaction {
             Interaction::Pressed => {
+                let Some(owner) = menus.owner_of(entity) else {
+                    continue;
+                };
+                let Some(MenuContext {
+                    menu_state,
+                    selections,
+                    editing,
+                    capture,
+                    ..
+                }) = menus.get(owner)
+                else {
+                    continue;
+                };
+                // Clicking a menu gives it the focus
+                focus.focus(MenuId::of::<S>(owner));
+                // The click was bound to a key binding, or the menu is
+                // behind a confirmation dialog
+                if capture.captured || menu_state.menu.confirming().is_some() {
+                    continue;
+                }
+                let lifecycle = Lifecycle::capture(&menu_state.menu, selections);
                 // pop to the chosen selection stack entry
                 menu_state.menu.pop_to_selection(selection);
 
@@ -141,15 +801,22 @@ pub fn mouse_system<S>(
                 selections
                     .0
                     .insert(menu_identifier.0.clone(), menu_identifier.1);
+                if let Some((text_input, text)) = menu_state.menu.focused_text_input(selections) {
+                    editing.start(text_input, text);
+                }
+                if let Some(binding) = menu_state.menu.focused_key_binding(selections) {
+                    capture.start(binding);
+                }
                 if let Some(current) = menu_state
                     .menu
-                    .apply_event(&NavigationEvent::Select, &mut selections)
+                    .apply_event(&NavigationEvent::Select, selections)
                 {
                     menu_state
                         .menu
                         .handle_selection(&current, &mut event_writer);
-                    redraw_writer.send(RedrawEvent);
                 }
+                lifecycle.emit(&menu_state.menu, selections, &mut lifecycle_writers, true);
+                menu_state.redraw_requested = true;
             }
             Interaction::Hovered => {
                 if !selected {
@@ -165,20 +832,447 @@ pub fn mouse_system<S>(
     }
 }
 
+/// Clicking the back button of the `SingleScreen` layout navigates back
+#[allow(clippy::type_complexity)]
+pub fn back_button_system<S>(
+    mut menus: Menus<S>,
+    mut buttons: Query<
+        (
+            Entity,
+            &Interaction,
+            &BackButtonComponent,
+            &mut BackgroundColor,
+        ),
+        Changed<Interaction>,
+    >,
+    mut event_writer: EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut warning_writer: EventWriter<MenuWarning>,
+    mut lifecycle_writers: LifecycleWriters<S>,
+) where
+    S: ScreenTrait + 'static,
+{
+    for (entity, interaction, BackButtonComponent { style }, mut background_color) in &mut buttons {
+        match *interaction {
+            Interaction::Pressed => {
+                if let Some(mut context) = menus.owner_of(entity).and_then(|owner| menus.get(owner))
+                {
+                    navigate(
+                        &mut context,
+                        &NavigationEvent::Back,
+                        &mut event_writer,
+                        &mut warning_writer,
+                        &mut lifecycle_writers,
+                    );
+                }
+            }
+            Interaction::Hovered => background_color.0 = style.hover.bg,
+            Interaction::None => background_color.0 = style.normal.bg,
+        }
+    }
+}
+
+/// Clicking a button of the confirmation dialog answers it
+#[allow(clippy::type_complexity)]
+pub fn dialog_button_system<S>(
+    mut menus: Menus<S>,
+    mut buttons: Query<
+        (
+            Entity,
+            &Interaction,
+            &DialogButtonComponent,
+            &mut BackgroundColor,
+        ),
+        Changed<Interaction>,
+    >,
+    mut event_writer: EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut warning_writer: EventWriter<MenuWarning>,
+    mut lifecycle_writers: LifecycleWriters<S>,
+) where
+    S: ScreenTrait + 'static,
+{
+    for (
+        entity,
+        interaction,
+        DialogButtonComponent {
+            answer,
+            style,
+            focused,
+        },
+        mut background_color,
+    ) in &mut buttons
+    {
+        match *interaction {
+            Interaction::Pressed => {
+                if let Some(mut context) = menus.owner_of(entity).and_then(|owner| menus.get(owner))
+                {
+                    context.menu_state.menu.focus_dialog(*answer);
+                    navigate(
+                        &mut context,
+                        &NavigationEvent::Select,
+                        &mut event_writer,
+                        &mut warning_writer,
+                        &mut lifecycle_writers,
+                    );
+                }
+            }
+            Interaction::Hovered if !focused => background_color.0 = style.hover.bg,
+            Interaction::None if !focused => background_color.0 = style.normal.bg,
+            _ => (),
+        }
+    }
+}
+
+/// Clicking a title in the breadcrumb goes back to its screen
+#[allow(clippy::type_complexity)]
+pub fn breadcrumb_system<S>(
+    mut menus: Menus<S>,
+    mut crumbs: Query<
+        (
+            Entity,
+            &Interaction,
+            &BreadcrumbComponent<S>,
+            &mut BackgroundColor,
+            &Children,
+        ),
+        Changed<Interaction>,
+    >,
+    mut texts: Query<&mut Text>,
+    mut lifecycle_writers: LifecycleWriters<S>,
+) where
+    S: ScreenTrait + 'static,
+{
+    for (
+        entity,
+        interaction,
+        BreadcrumbComponent { selection, style },
+        mut background_color,
+        children,
+    ) in &mut crumbs
+    {
+        let state = match *interaction {
+            Interaction::Pressed => {
+                if let Some(MenuContext {
+                    menu_state,
+                    selections,
+                    ..
+                }) = menus.owner_of(entity).and_then(|owner| menus.get(owner))
+                {
+                    let lifecycle = Lifecycle::capture(&menu_state.menu, selections);
+                    menu_state.menu.pop_to_selection(selection);
+                    lifecycle.emit(&menu_state.menu, selections, &mut lifecycle_writers, false);
+                    menu_state.redraw_requested = true;
+                }
+                continue;
+            }
+            Interaction::Hovered => style.hover,
+            Interaction::None => style.normal,
+        };
+        background_color.0 = state.bg;
+        let mut iter = texts.iter_many_mut(children);
+        while let Some(mut text) = iter.fetch_next() {
+            for section in text.sections.iter_mut() {
+                section.style.color = state.fg;
+            }
+        }
+    }
+}
+
+/// Scroll the hovered vertical menu with the mouse wheel
+pub fn scroll_system<S>(
+    mut wheel_reader: EventReader<MouseWheel>,
+    vertical_menus: Query<(Entity, &VerticalMenuComponent, &Node, &GlobalTransform)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut menus: Menus<S>,
+) where
+    S: ScreenTrait + 'static,
+{
+    // Pixel based scrolling (e.g. touchpads) is converted into rows
+    const PIXELS_PER_ROW: f32 = 20.0;
+    let delta: f32 = wheel_reader
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_ROW,
+        })
+        .sum();
+    if delta == 0.0 {
+        return;
+    }
+    let Some(cursor) = windows.get_single().ok().and_then(|w| w.cursor_position()) else {
+        return;
+    };
+    for (entity, VerticalMenuComponent(id), node, transform) in vertical_menus.iter() {
+        if !node.logical_rect(transform).contains(cursor) {
+            continue;
+        }
+        let Some(MenuContext {
+            menu_state,
+            scrolls,
+            ..
+        }) = menus.owner_of(entity).and_then(|owner| menus.get(owner))
+        else {
+            continue;
+        };
+        let Some(scroll) = scrolls.0.get_mut(id) else {
+            continue;
+        };
+        let previous = scroll.offset;
+        // Scrolling up (positive) reduces the offset
+        scroll.scroll_by(-delta.round() as isize);
+        if scroll.offset != previous {
+            menu_state.redraw_requested = true;
+        }
+    }
+}
+
+/// Smooth the progress of a transition
+fn ease(progress: f32) -> f32 {
+    let t = progress.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Animate the vertical menus that enter or leave the menu row.
+/// Leaving menus are despawned once they are hidden.
+#[allow(clippy::type_complexity)]
+pub fn screen_transition_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut menus: Query<(Entity, &mut MenuTransition, &mut Style, &mut Transform)>,
+    children: Query<&Children>,
+    mut colors: Query<(
+        Option<&mut BackgroundColor>,
+        Option<&mut UiImage>,
+        Option<&mut Text>,
+        Option<&FadeOrigin>,
+    )>,
+) {
+    for (entity, mut transition, mut style, mut transform) in &mut menus {
+        transition.elapsed += time.delta_seconds();
+        let done = transition.elapsed >= transition.duration;
+        if done && transition.leaving {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let progress = ease(transition.elapsed / transition.duration);
+        let visible = if transition.leaving {
+            1.0 - progress
+        } else {
+            progress
+        };
+        match transition.transition {
+            ScreenTransition::None => (),
+            ScreenTransition::Slide => {
+                let left = *transition.left.get_or_insert(style.left);
+                style.left = if done {
+                    left
+                } else {
+                    Val::Px(transition.distance * (1.0 - visible))
+                };
+            }
+            ScreenTransition::Fade => {
+                for faded in std::iter::once(entity).chain(children.iter_descendants(entity)) {
+                    let Ok((background, image, text, origin)) = colors.get_mut(faded) else {
+                        continue;
+                    };
+                    // The colors are remembered the first time an entity fades,
+                    // rows that are re-created during the transition join in
+                    let origin = match origin {
+                        Some(origin) => origin.clone(),
+                        None => {
+                            let origin = FadeOrigin {
+                                background: background.as_ref().map(|b| b.0),
+                                image: image.as_ref().map(|i| i.color),
+                                text: text
+                                    .as_ref()
+                                    .map(|t| t.sections.iter().map(|s| s.style.color).collect())
+                                    .unwrap_or_default(),
+                            };
+                            commands.entity(faded).try_insert(origin.clone());
+                            origin
+                        }
+                    };
+                    let fade = |color: Color| color.with_alpha(color.alpha() * visible);
+                    if let (Some(mut background), Some(color)) = (background, origin.background) {
+                        background.0 = fade(color);
+                    }
+                    if let (Some(mut image), Some(color)) = (image, origin.image) {
+                        image.color = fade(color);
+                    }
+                    if let Some(mut text) = text {
+                        for (section, color) in text.sections.iter_mut().zip(&origin.text) {
+                            section.style.color = fade(*color);
+                        }
+                    }
+                    if done {
+                        commands.entity(faded).remove::<FadeOrigin>();
+                    }
+                }
+            }
+            ScreenTransition::Scale => transform.scale = Vec3::splat(visible),
+        }
+        if done {
+            commands.entity(entity).remove::<MenuTransition>();
+        }
+    }
+}
+
+/// Animate the background of buttons that gained or lost the focus
+pub fn focus_transition_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rows: Query<(Entity, &mut FocusTransition, &Children)>,
+    mut buttons: Query<&mut BackgroundColor, With<Button>>,
+) {
+    for (entity, mut transition, children) in &mut rows {
+        transition.elapsed += time.delta_seconds();
+        let progress = ease(transition.elapsed / transition.duration);
+        let mut iter = buttons.iter_many_mut(children);
+        while let Some(mut background) = iter.fetch_next() {
+            background.0 = transition.from.mix(&transition.to, progress);
+        }
+        if transition.elapsed >= transition.duration {
+            commands.entity(entity).remove::<FocusTransition>();
+        }
+    }
+}
+
+/// Show the tooltip of a row while the mouse is over the row, unless
+/// a confirmation dialog is open
+pub fn tooltip_system(
+    rows: Query<(&RelativeCursorPosition, &Children), With<TooltipAnchor>>,
+    mut tooltips: Query<&mut Visibility, With<TooltipComponent>>,
+    dialogs: Query<(), With<RenderedDialog>>,
+) {
+    for (cursor, children) in &rows {
+        let visibility = if cursor.mouse_over() && dialogs.is_empty() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        let mut iter = tooltips.iter_many_mut(children);
+        while let Some(mut tooltip) = iter.fetch_next() {
+            tooltip.set_if_neq(visibility);
+        }
+    }
+}
+
+/// Pressing the mouse on a slider track and dragging it changes the value.
+/// The dragged slider is remembered by its menu and identifier, as the track
+/// entities are re-created on every redraw.
+#[allow(clippy::type_complexity)]
+pub fn slider_drag_system<S>(
+    mut menus: Menus<S>,
+    tracks: Query<(Entity, &SliderComponent<S>, &Node, &GlobalTransform)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut dragging: Local<Option<(Option<Entity>, (WidgetId, usize))>>,
+    mut event_writer: EventWriter<<<S as ScreenTrait>::Action as ActionTrait>::Event>,
+    mut sound_writer: EventWriter<MenuSound>,
+) where
+    S: ScreenTrait + 'static,
+{
+    if !mouse_buttons.pressed(MouseButton::Left) {
+        *dragging = None;
+        return;
+    }
+    let Some(cursor) = windows.get_single().ok().and_then(|w| w.cursor_position()) else {
+        return;
+    };
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        *dragging = tracks
+            .iter()
+            .find(|(_, _, node, transform)| node.logical_rect(transform).contains(cursor))
+            .and_then(|(entity, slider, _, _)| {
+                Some((menus.owner_of(entity)?, slider.menu_identifier.clone()))
+            });
+    }
+    let Some((owner, identifier)) = dragging.clone() else {
+        return;
+    };
+    let Some((_, slider, node, transform)) = tracks.iter().find(|(entity, slider, _, _)| {
+        slider.menu_identifier == identifier && menus.owner_of(*entity) == Some(owner)
+    }) else {
+        return;
+    };
+    let Some(MenuContext {
+        menu_state,
+        selections,
+        ..
+    }) = menus.get(owner)
+    else {
+        return;
+    };
+
+    let rect = node.logical_rect(transform);
+    let fraction = if rect.width() > 0.0 {
+        (cursor.x - rect.min.x) / rect.width()
+    } else {
+        0.0
+    };
+    let value = slider.value.value_at(fraction);
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        menu_state
+            .menu
+            .pop_to_selection(&MenuSelection::Slider(slider.action));
+        selections.0.insert(identifier.0.clone(), identifier.1);
+        menu_state.redraw_requested = true;
+    }
+    if value != slider.value.value {
+        slider
+            .action
+            .set_value(value, menu_state.state_mut(), &mut event_writer);
+        sound_writer.send(MenuSound::ValueChange);
+    }
+}
+
 /// If the `CleanUpUI` `Resource` is available, remove the menu and then the resource.
 /// This is used to close the menu when it is not needed anymore.
 pub fn cleanup_system<S>(
     mut commands: Commands,
-    existing: Query<Entity, With<types::QuickMenuComponent>>,
+    existing: Query<Entity, (With<types::QuickMenuComponent>, Without<Parent>)>,
+    menu_state: Option<Res<MenuState<S>>>,
+    mut closed_writer: EventWriter<MenuClosed>,
 ) where
     S: ScreenTrait + 'static,
 {
-    // Remove all menu elements
+    // Remove all menu elements. Those of `QuickMenu` instances are
+    // children of their entity and stay.
     for item in existing.iter() {
         commands.entity(item).despawn_recursive();
     }
     // Remove the resource again
     commands.remove_resource::<CleanUpUI>();
-    // Remove the state
-    commands.remove_resource::<MenuState<S>>();
+    // Remove the state. Only a menu that was open is reported as closed.
+    if menu_state.is_some() {
+        commands.remove_resource::<MenuState<S>>();
+        closed_writer.send(MenuClosed);
+    }
+}
+
+/// Remove the rendered menu of `QuickMenu` instances whose component was
+/// removed. If the entity was despawned, the menu is already gone and
+/// `MenuClosed` isn't sent.
+pub fn quick_menu_cleanup_system<S>(
+    mut commands: Commands,
+    mut removed: RemovedComponents<QuickMenu<S>>,
+    primaries: Query<(Entity, &MenuOwner<S>)>,
+    mut closed_writer: EventWriter<MenuClosed>,
+) where
+    S: ScreenTrait + 'static,
+{
+    for instance in removed.read() {
+        // Only a menu that was rendered is reported as closed
+        let mut closed = false;
+        for (primary, owner) in primaries.iter() {
+            if owner.owner == Some(instance) {
+                commands.entity(primary).despawn_recursive();
+                closed = true;
+            }
+        }
+        if closed {
+            closed_writer.send(MenuClosed);
+        }
+    }
 }
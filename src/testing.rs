@@ -0,0 +1,299 @@
+//! Headless testing
+//! Available with the `testing` feature. `MenuTester` runs a menu in a
+//! minimal `App` without a window or GPU. Tests can send `NavigationEvent`s
+//! and keys, advance frames and inspect the screen stack, the focused item,
+//! the rendered labels and the emitted events.
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_quickmenu::testing::MenuTester;
+//! # use bevy_quickmenu::{ActionTrait, Menu, MenuItem, NavigationEvent, ScreenTrait};
+//! # #[derive(Debug, Event)]
+//! # struct BasicEvent;
+//! # #[derive(Debug, Default)]
+//! # struct BasicState {
+//! #     boolean: bool,
+//! # }
+//! # #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+//! # enum Actions {
+//! #     Toggle,
+//! # }
+//! # impl ActionTrait for Actions {
+//! #     type State = BasicState;
+//! #     type Event = BasicEvent;
+//! #     fn handle(&self, state: &mut BasicState, _: &mut EventWriter<BasicEvent>) {
+//! #         state.boolean = !state.boolean;
+//! #     }
+//! # }
+//! # #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+//! # enum Screens {
+//! #     Root,
+//! #     Booleans,
+//! # }
+//! # impl ScreenTrait for Screens {
+//! #     type Action = Actions;
+//! #     type State = BasicState;
+//! #     fn resolve(&self, state: &BasicState) -> Menu<Screens> {
+//! #         match self {
+//! #             Screens::Root => Menu::new(
+//! #                 "root",
+//! #                 vec![
+//! #                     MenuItem::headline("Basic Example"),
+//! #                     MenuItem::screen("Boolean", Screens::Booleans),
+//! #                 ],
+//! #             ),
+//! #             Screens::Booleans => Menu::new(
+//! #                 "boolean",
+//! #                 vec![
+//! #                     MenuItem::label("Toggles some booleans"),
+//! #                     MenuItem::action("Toggle", Actions::Toggle).checked(state.boolean),
+//! #                 ],
+//! #             ),
+//! #         }
+//! #     }
+//! # }
+//! let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+//! tester.send(NavigationEvent::Select);
+//! assert_eq!(tester.stack(), &[Screens::Root, Screens::Booleans]);
+//! assert_eq!(tester.labels()[1][0], "Toggles some booleans");
+//!
+//! tester.send(NavigationEvent::Select);
+//! assert!(tester.state().boolean);
+//! ```
+
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey, NativeKeyCode};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+use crate::localization::MenuLocalization;
+use crate::snapshot::MenuSnapshot;
+use crate::types::{
+    MenuItem, MenuOptions, MenuTransition, MenuWarning, NavigationEvent, PrimaryMenu,
+    VerticalMenuComponent,
+};
+use crate::{ActionTrait, MenuResources, MenuState, QuickMenuPlugin, ScreenTrait};
+
+type ActionEvent<S> = <<S as ScreenTrait>::Action as ActionTrait>::Event;
+
+pub struct MenuTester<S>
+where
+    S: ScreenTrait + 'static,
+{
+    app: App,
+    events: Vec<ActionEvent<S>>,
+    warnings: Vec<MenuWarning>,
+}
+
+impl<State, A, S> MenuTester<S>
+where
+    State: 'static + Send + Sync,
+    A: ActionTrait<State = State> + 'static,
+    S: ScreenTrait<Action = A, State = State> + 'static,
+{
+    /// Open a menu with the default options and render it
+    pub fn new(state: State, root: S) -> Self {
+        Self::with_options(MenuOptions::default(), state, root)
+    }
+
+    /// Open a menu with custom options and render it
+    pub fn with_options(options: MenuOptions, state: State, root: S) -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            bevy::input::InputPlugin,
+            HierarchyPlugin,
+        ))
+        .init_asset::<Font>()
+        .init_asset::<Image>()
+        .add_event::<ActionEvent<S>>()
+        .add_plugins(QuickMenuPlugin::<S>::with_options(options))
+        .insert_resource(MenuState::new(state, root, None));
+        let mut tester = Self {
+            app,
+            events: Vec::new(),
+            warnings: Vec::new(),
+        };
+        tester.update();
+        tester
+    }
+
+    /// The app, e.g. in order to add additional systems or resources
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// Run a single frame
+    pub fn update(&mut self) -> &mut Self {
+        self.app.update();
+        // Collect the events before they are dropped after two frames
+        let events = self
+            .app
+            .world_mut()
+            .resource_mut::<Events<ActionEvent<S>>>()
+            .drain()
+            .collect::<Vec<_>>();
+        self.events.extend(events);
+        let warnings = self
+            .app
+            .world_mut()
+            .resource_mut::<Events<MenuWarning>>()
+            .drain()
+            .collect::<Vec<_>>();
+        self.warnings.extend(warnings);
+        self
+    }
+
+    /// Run a number of frames
+    pub fn advance(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    /// Send a navigation event and run the frames that process and render it
+    pub fn send(&mut self, event: NavigationEvent) -> &mut Self {
+        self.app.world_mut().send_event(event);
+        // The redraw that is requested by the input is rendered in the next frame
+        self.advance(2)
+    }
+
+    /// Press and release a key, e.g. in order to end editing a text input or
+    /// to bind it to the key binding that is waiting for an input
+    pub fn press_key(&mut self, key_code: KeyCode) -> &mut Self {
+        let logical_key = match key_code {
+            KeyCode::Enter => Key::Enter,
+            KeyCode::Escape => Key::Escape,
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Space => Key::Space,
+            _ => Key::Unidentified(NativeKey::Unidentified),
+        };
+        self.send_key(key_code, logical_key)
+    }
+
+    /// Type text into the text input that is being edited, one key per frame
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            let key = KeyCode::Unidentified(NativeKeyCode::Unidentified);
+            self.send_key(key, Key::Character(c.to_string().into()));
+        }
+        self
+    }
+
+    fn send_key(&mut self, key_code: KeyCode, logical_key: Key) -> &mut Self {
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            self.app.world_mut().send_event(KeyboardInput {
+                key_code,
+                logical_key: logical_key.clone(),
+                state,
+                window: Entity::PLACEHOLDER,
+            });
+            self.update();
+        }
+        // The redraw that is requested by the input is rendered in the next frame
+        self.update()
+    }
+
+    /// Send multiple navigation events, one per frame
+    pub fn send_all(&mut self, events: impl IntoIterator<Item = NavigationEvent>) -> &mut Self {
+        for event in events {
+            self.send(event);
+        }
+        self
+    }
+
+    pub fn menu_state(&self) -> &MenuState<S> {
+        self.app.world().resource::<MenuState<S>>()
+    }
+
+    pub fn state(&self) -> &State {
+        self.menu_state().state()
+    }
+
+    /// The screens on the stack, the top-most screen last
+    pub fn stack(&self) -> &[S] {
+        self.menu_state().stack()
+    }
+
+    /// The focused item of the top-most screen
+    pub fn focused_item(&self) -> Option<MenuItem<S>> {
+        let selections = &self.app.world().resource::<MenuResources<S>>().selections;
+        self.menu_state().menu.focused_item(selections)
+    }
+
+    /// A snapshot of the current menu, in the language of the
+    /// `MenuLocalization` if there is one
+    pub fn snapshot(&self) -> MenuSnapshot {
+        let selections = &self.app.world().resource::<MenuResources<S>>().selections;
+        let localization = self.app.world().get_resource::<MenuLocalization>();
+        self.menu_state().snapshot(selections, localization)
+    }
+
+    /// The events that were emitted by actions so far
+    pub fn events(&self) -> &[ActionEvent<S>] {
+        &self.events
+    }
+
+    /// Take the events that were emitted by actions so far
+    pub fn take_events(&mut self) -> Vec<ActionEvent<S>> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Take the warnings that were reported so far
+    pub fn take_warnings(&mut self) -> Vec<MenuWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// The rendered texts of each visible screen, one entry per row.
+    /// The texts within a row (e.g. the label and value of a slider) are
+    /// separated by a space. Menus that are leaving and hidden texts
+    /// (e.g. tooltips) are skipped.
+    pub fn labels(&self) -> Vec<Vec<String>> {
+        let world = self.app.world();
+        let Some(menus) = world
+            .iter_entities()
+            .find(|entity| entity.contains::<PrimaryMenu>())
+            .and_then(|primary| primary.get::<Children>())
+        else {
+            return Vec::new();
+        };
+        menus
+            .iter()
+            .filter(|menu| world.get::<VerticalMenuComponent>(**menu).is_some())
+            .filter(|menu| {
+                !world
+                    .get::<MenuTransition>(**menu)
+                    .is_some_and(|transition| transition.leaving)
+            })
+            .map(|menu| {
+                world
+                    .get::<Children>(*menu)
+                    .map(|rows| {
+                        rows.iter()
+                            .map(|row| {
+                                let mut texts = Vec::new();
+                                collect_texts(world, *row, &mut texts);
+                                texts.join(" ")
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+fn collect_texts(world: &World, entity: Entity, texts: &mut Vec<String>) {
+    if world.get::<Visibility>(entity) == Some(&Visibility::Hidden) {
+        return;
+    }
+    if let Some(text) = world.get::<Text>(entity) {
+        texts.push(text.sections.iter().map(|s| s.value.as_str()).collect());
+    }
+    if let Some(children) = world.get::<Children>(entity) {
+        for child in children {
+            collect_texts(world, *child, texts);
+        }
+    }
+}
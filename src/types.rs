@@ -1,6 +1,9 @@
 use std::borrow::Cow;
 use std::hash::Hash;
 
+use crate::input::{BoundInput, InputDevice, MenuInputMap};
+use crate::localization::{LocalizationArg, MenuLocalization};
+use crate::style::{ScreenTransition, Stylesheet, Transitions};
 use crate::ScreenTrait;
 use bevy::prelude::*;
 use bevy::render::render_asset::RenderAssetUsages;
@@ -10,6 +13,39 @@ use bevy::utils::HashMap;
 #[derive(Component)]
 pub struct QuickMenuComponent;
 
+/// The menu a rendered `PrimaryMenu` belongs to: the `MenuState` resource
+/// (`None`) or the entity of a `QuickMenu` instance
+#[derive(Component)]
+pub(crate) struct MenuOwner<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub owner: Option<Entity>,
+    screen: std::marker::PhantomData<S>,
+}
+
+impl<S> MenuOwner<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub fn new(owner: Option<Entity>) -> Self {
+        Self {
+            owner,
+            screen: Default::default(),
+        }
+    }
+}
+
+/// Marks the text input that is being edited. While one exists, the
+/// keyboard is used for typing instead of navigation.
+#[derive(Component)]
+pub(crate) struct EditingTextInput;
+
+/// Marks the key binding that is waiting for an input. While one exists,
+/// the keyboard and gamepads don't navigate the menu.
+#[derive(Component)]
+pub(crate) struct CapturingBinding;
+
 /// The primary horizontal menu can be queried via this component
 #[derive(Component)]
 pub struct PrimaryMenu;
@@ -18,6 +54,169 @@ pub struct PrimaryMenu;
 #[derive(Component)]
 pub struct VerticalMenuComponent(pub WidgetId);
 
+/// The stylesheet the primary menu was rendered with. If it changes,
+/// the whole menu is rebuilt.
+#[derive(Component)]
+pub(crate) struct RenderedStylesheet(pub Stylesheet);
+
+/// What the header above the screens was rendered with
+#[derive(Component)]
+pub(crate) struct RenderedHeader<S>
+where
+    S: ScreenTrait + 'static,
+{
+    /// The titles of the stacked screens with the selection that goes back
+    /// to them. The current screen has no selection.
+    pub crumbs: Vec<(WidgetLabel, Option<MenuSelection<S>>)>,
+    pub back: Option<String>,
+}
+
+impl<S> PartialEq for RenderedHeader<S>
+where
+    S: ScreenTrait + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.crumbs == other.crumbs && self.back == other.back
+    }
+}
+
+/// What the description panel was rendered with: the description of the
+/// focused item, if it has one
+#[derive(Component, PartialEq)]
+pub(crate) struct RenderedDescription(pub Option<ItemDescription>);
+
+/// The confirmation dialog of a menu that waits for an answer
+#[derive(Debug, Clone)]
+pub(crate) struct OpenDialog<S>
+where
+    S: ScreenTrait,
+{
+    pub action: S::Action,
+    pub confirmation: Confirmation,
+    pub focus: ConfirmationAnswer,
+}
+
+/// What the confirmation dialog was rendered with
+#[derive(Component, PartialEq)]
+pub(crate) struct RenderedDialog {
+    pub confirmation: Confirmation,
+    pub focus: ConfirmationAnswer,
+}
+
+/// A button of the confirmation dialog
+#[derive(Component)]
+pub(crate) struct DialogButtonComponent {
+    pub answer: ConfirmationAnswer,
+    pub style: crate::style::StyleEntry,
+    pub focused: bool,
+}
+
+/// A row that shows the description of its item in a tooltip
+#[derive(Component)]
+pub(crate) struct TooltipAnchor;
+
+/// The tooltip of a row, only visible while the mouse is over the row
+#[derive(Component)]
+pub(crate) struct TooltipComponent;
+
+/// A title in the breadcrumb that goes back to its screen when clicked
+#[derive(Component)]
+pub(crate) struct BreadcrumbComponent<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub selection: MenuSelection<S>,
+    pub style: crate::style::StyleEntry,
+}
+
+/// The back button in the header of the `SingleScreen` layout
+#[derive(Component)]
+pub(crate) struct BackButtonComponent {
+    pub style: crate::style::StyleEntry,
+}
+
+/// What a vertical menu node was rendered with
+#[derive(Component)]
+pub(crate) struct RenderedMenu {
+    pub id: WidgetId,
+    pub style: Style,
+    pub background: BackgroundColor,
+}
+
+/// A vertical menu that is entering the menu row, or leaving it after its
+/// screen was popped. Leaving menus are despawned once the transition ends.
+#[derive(Component)]
+pub(crate) struct MenuTransition {
+    pub transition: ScreenTransition,
+    pub duration: f32,
+    pub distance: f32,
+    pub elapsed: f32,
+    pub leaving: bool,
+    /// The `left` of the menu's style before it started sliding
+    pub left: Option<Val>,
+}
+
+impl MenuTransition {
+    pub fn new(transitions: &Transitions, leaving: bool) -> Self {
+        Self {
+            transition: transitions.screen,
+            duration: transitions.screen_duration,
+            distance: transitions.slide_distance,
+            elapsed: 0.0,
+            leaving,
+            left: None,
+        }
+    }
+}
+
+/// The colors of an entity within a fading menu before it started fading
+#[derive(Component, Clone)]
+pub(crate) struct FadeOrigin {
+    pub background: Option<Color>,
+    pub image: Option<Color>,
+    pub text: Vec<Color>,
+}
+
+/// Animates the background of the button in a row from the previous
+/// focus state to the current one
+#[derive(Component)]
+pub(crate) struct FocusTransition {
+    pub from: Color,
+    pub to: Color,
+    pub duration: f32,
+    pub elapsed: f32,
+}
+
+/// What a row in a vertical menu was rendered with. Rows are keyed by the
+/// position of their item in the menu, also while the menu scrolls, and
+/// are only re-created if any of this changes.
+#[derive(Component)]
+pub(crate) struct RenderedItem<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub item: MenuItem<S>,
+    pub selectable_index: usize,
+    pub focused: bool,
+    /// A text input that is edited or a key binding that is capturing
+    pub editing: bool,
+    /// A key binding whose input is also bound by another one
+    pub conflict: bool,
+}
+
+impl<S> PartialEq for RenderedItem<S>
+where
+    S: ScreenTrait + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.item == other.item
+            && self.selectable_index == other.selectable_index
+            && self.focused == other.focused
+            && self.editing == other.editing
+            && self.conflict == other.conflict
+    }
+}
+
 /// Each Button in the UI can be queried via this component in order
 /// to further change the appearance
 #[derive(Component)]
@@ -30,6 +229,7 @@ where
     pub selection: MenuSelection<S>,
     pub menu_identifier: (WidgetId, usize),
     pub selected: bool,
+    pub disabled: bool,
 }
 
 /// Helper to remove the Menu. This `Resource` is inserted to notify
@@ -38,9 +238,58 @@ where
 pub struct CleanUpUI;
 
 /// This map holds the currently selected items in each screen / menu
-#[derive(Resource, Default)]
+#[derive(Default)]
 pub struct Selections(pub HashMap<WidgetId, usize>);
 
+/// This map holds the scroll state of each screen / menu that
+/// only shows a limited number of rows
+#[derive(Default)]
+pub struct ScrollOffsets(pub HashMap<WidgetId, ScrollOffset>);
+
+/// The scroll state of a single menu
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScrollOffset {
+    /// The index of the first visible item
+    pub offset: usize,
+    /// The largest possible offset
+    pub max_offset: usize,
+    /// The item position that was focused during the last render
+    focused: Option<usize>,
+}
+
+impl ScrollOffset {
+    /// Scroll by a number of rows, e.g. via the mouse wheel
+    pub fn scroll_by(&mut self, rows: isize) {
+        self.offset = self.offset.saturating_add_signed(rows).min(self.max_offset);
+    }
+
+    /// Clamp the offset and, if the focus moved, scroll the focused item into view
+    pub(crate) fn update(
+        &mut self,
+        rows: usize,
+        items: usize,
+        focused: Option<usize>,
+        first_selectable: Option<usize>,
+        last_selectable: Option<usize>,
+    ) {
+        self.max_offset = items.saturating_sub(rows);
+        if let Some(position) = focused.filter(|_| focused != self.focused) {
+            if focused == first_selectable {
+                // Also reveal the labels above the first entry
+                self.offset = 0;
+            } else if focused == last_selectable {
+                self.offset = self.max_offset;
+            } else if position < self.offset {
+                self.offset = position;
+            } else if position >= self.offset + rows {
+                self.offset = position + 1 - rows;
+            }
+        }
+        self.focused = focused;
+        self.offset = self.offset.min(self.max_offset);
+    }
+}
+
 /// GamePad and Cursor navigation generates these navigation events
 /// which are then processed by a system and applied to the menu.
 /// Navigation can be customized by sending these events into a
@@ -49,8 +298,149 @@ pub struct Selections(pub HashMap<WidgetId, usize>);
 pub enum NavigationEvent {
     Up,
     Down,
+    /// Horizontal input. It is first offered to the focused item
+    /// (see [`crate::ActionTrait::adjust`]). If the item doesn't consume it,
+    /// `Left` behaves like `Back`.
+    Left,
+    Right,
+    Select,
+    Back,
+}
+
+impl NavigationEvent {
+    /// `Up`, `Down`, `Left` and `Right` repeat when the input is held
+    pub fn is_directional(&self) -> bool {
+        matches!(
+            self,
+            NavigationEvent::Up
+                | NavigationEvent::Down
+                | NavigationEvent::Left
+                | NavigationEvent::Right
+        )
+    }
+
+    /// The value adjustment that this event represents, if any
+    pub fn adjustment(&self) -> Option<Adjustment> {
+        match self {
+            NavigationEvent::Left => Some(Adjustment::Decrement),
+            NavigationEvent::Right => Some(Adjustment::Increment),
+            _ => None,
+        }
+    }
+}
+
+/// A `NavigationEvent` together with the device it came from. These are sent
+/// by the built-in input handling, so that menus which belong to a specific
+/// device only react to it. Each one is mirrored as a plain `NavigationEvent`
+/// for the systems that don't care about the device. Plain `NavigationEvent`s
+/// sent by other systems go to the focused menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct DeviceNavigationEvent {
+    pub event: NavigationEvent,
+    pub device: InputDevice,
+}
+
+/// The `NavigationEvent`s that mirror a `DeviceNavigationEvent`, so that
+/// the menus only apply the device event
+#[derive(Resource, Default)]
+pub(crate) struct MirroredNavigation(pub Vec<bevy::ecs::event::EventId<NavigationEvent>>);
+
+/// Horizontal navigation on a focused item is handed to
+/// [`crate::ActionTrait::adjust`] as one of these values. This allows
+/// changing values (e.g. volume or difficulty) in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Adjustment {
+    Increment,
+    Decrement,
+}
+
+/// The track of a slider. Used to map mouse drags onto slider values
+#[derive(Component)]
+pub struct SliderComponent<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub action: S::Action,
+    pub value: SliderValue,
+    pub menu_identifier: (WidgetId, usize),
+}
+
+/// Sent when the menu is rendered for the first time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct MenuOpened;
+
+/// Sent when the `MenuState` menu is removed via [`crate::cleanup`] or the
+/// `QuickMenu` component of a rendered instance is removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct MenuClosed;
+
+/// Sent when a screen is opened on top of the stack, via the input or
+/// the `MenuState`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct ScreenPushed<S>(pub S)
+where
+    S: ScreenTrait + 'static;
+
+/// Sent when a screen is removed from the top of the stack, via the input
+/// or the `MenuState`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct ScreenPopped<S>(pub S)
+where
+    S: ScreenTrait + 'static;
+
+/// Sent when the focused item of the top-most screen changes
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct FocusChanged<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub old: MenuSelection<S>,
+    pub new: MenuSelection<S>,
+}
+
+/// Sent when a `MenuItem::KeyBinding` is bound to an input that is already
+/// bound by other key bindings of the same screen. The new binding is still
+/// reported via [`crate::ActionTrait::set_binding`], so that the conflicting
+/// bindings can e.g. be swapped or cleared.
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct BindingConflict<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub action: S::Action,
+    pub input: BoundInput,
+    pub conflicts: Vec<S::Action>,
+}
+
+/// The feedback for a navigation, e.g. in order to play sounds. With the
+/// `audio` feature, the `MenuSounds` of the `MenuOptions` are played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Event)]
+pub enum MenuSound {
+    /// The focus moved to another item
+    Focus,
+    /// An item was selected or a screen was opened
     Select,
+    /// A screen was closed
     Back,
+    /// A disabled item was selected or the screen has no selectable items
+    Error,
+    /// The value of a slider or an adjustable action changed
+    ValueChange,
+}
+
+/// Conditions in which the menu can't behave as expected. They are
+/// reported instead of panicking, e.g. in order to log them.
+#[derive(Debug, Clone, PartialEq, Eq, Event)]
+pub enum MenuWarning {
+    /// The menu has no selectable items. Only `Back` can be used
+    NoSelectableItems(WidgetId),
+    /// The selection was out of range (e.g. because the menu shrunk)
+    /// and was moved to the last selectable item
+    StaleSelection {
+        menu: WidgetId,
+        index: usize,
+        selectables: usize,
+    },
 }
 
 /// Whenever a state change in the `MenuState` is detected,
@@ -67,6 +457,9 @@ where
     pub entries: Vec<MenuItem<S>>,
     pub style: Option<Style>,
     pub background: Option<BackgroundColor>,
+    pub visible_rows: Option<usize>,
+    /// Shown in the breadcrumb of the `Stylesheet`
+    pub title: Option<WidgetLabel>,
 }
 
 impl<S> Menu<S>
@@ -80,6 +473,8 @@ where
             entries,
             style: None,
             background: None,
+            visible_rows: None,
+            title: None,
         }
     }
 
@@ -92,19 +487,41 @@ where
         self.style = Some(style);
         self
     }
+
+    /// Only show this many items at once. The menu scrolls to keep
+    /// the focused item visible and can be scrolled with the mouse wheel.
+    pub fn with_visible_rows(mut self, rows: usize) -> Self {
+        self.visible_rows = Some(rows);
+        self
+    }
+
+    pub fn with_title(mut self, title: impl Into<WidgetLabel>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
 }
 
 /// Abstraction over MenuItems in a Screen / Menu
 #[allow(clippy::large_enum_variant)]
+#[derive(Clone, PartialEq)]
 pub enum MenuItem<S>
 where
     S: ScreenTrait,
 {
     Screen(WidgetLabel, MenuIcon, S),
     Action(WidgetLabel, MenuIcon, S::Action),
+    Slider(WidgetLabel, MenuIcon, S::Action, SliderValue),
+    TextInput(WidgetLabel, MenuIcon, S::Action, TextInputValue),
+    KeyBinding(WidgetLabel, MenuIcon, S::Action, KeyBindingValue),
     Label(WidgetLabel, MenuIcon),
     Headline(WidgetLabel, MenuIcon),
     Image(Handle<Image>, Option<Style>),
+    /// An item with a description, see `MenuItem::with_description`
+    Described(Box<MenuItem<S>>, ItemDescription),
+    /// An action that has to be confirmed, see `MenuItem::with_confirmation`
+    Confirmed(Box<MenuItem<S>>, Confirmation),
+    /// A screen or action that can't be selected, see `MenuItem::disabled`
+    Disabled(Box<MenuItem<S>>),
 }
 
 impl<S> MenuItem<S>
@@ -119,6 +536,107 @@ where
         MenuItem::Action(s.into(), MenuIcon::None, action)
     }
 
+    /// A slider for a numeric value in `range`. The `value` should be read from
+    /// the state. Changes are reported via [`crate::ActionTrait::set_value`].
+    pub fn slider(
+        s: impl Into<WidgetLabel>,
+        action: S::Action,
+        value: f32,
+        range: std::ops::RangeInclusive<f32>,
+        step: f32,
+    ) -> Self {
+        MenuItem::Slider(
+            s.into(),
+            MenuIcon::None,
+            action,
+            SliderValue {
+                value,
+                min: *range.start(),
+                max: *range.end(),
+                step,
+            },
+        )
+    }
+
+    /// A text field with the current `text` read from the state. Selecting it
+    /// starts editing, `Enter` ends it and `Escape` restores the previous
+    /// text. Changes are reported via
+    /// [`crate::ActionTrait::set_text`].
+    pub fn text_input(
+        s: impl Into<WidgetLabel>,
+        action: S::Action,
+        text: impl Into<String>,
+    ) -> Self {
+        MenuItem::TextInput(
+            s.into(),
+            MenuIcon::None,
+            action,
+            TextInputValue {
+                text: text.into(),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// The text shown in an empty `MenuItem::TextInput`
+    pub fn with_placeholder(self, placeholder: impl Into<String>) -> Self {
+        self.map_text_input(|value| value.placeholder = placeholder.into())
+    }
+
+    /// The maximum number of characters of a `MenuItem::TextInput`
+    pub fn with_max_length(self, max_length: usize) -> Self {
+        self.map_text_input(|value| value.max_length = Some(max_length))
+    }
+
+    /// The characters that can be entered into a `MenuItem::TextInput`
+    pub fn with_filter(self, filter: CharacterFilter) -> Self {
+        self.map_text_input(|value| value.filter = filter)
+    }
+
+    fn map_text_input(mut self, f: impl FnOnce(&mut TextInputValue)) -> Self {
+        if let MenuItem::TextInput(_, _, _, value) = self.inner_mut() {
+            f(value);
+        }
+        self
+    }
+
+    /// A control binding with the currently bound `input` read from the state.
+    /// Selecting it waits for the next key, mouse button or gamepad input,
+    /// `Escape` or the timeout cancel. Changes are reported via
+    /// [`crate::ActionTrait::set_binding`].
+    pub fn key_binding(
+        s: impl Into<WidgetLabel>,
+        action: S::Action,
+        input: Option<BoundInput>,
+    ) -> Self {
+        MenuItem::KeyBinding(
+            s.into(),
+            MenuIcon::None,
+            action,
+            KeyBindingValue {
+                input,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// An image that is shown for the bound input of a `MenuItem::KeyBinding`
+    /// instead of its name, e.g. a button glyph
+    pub fn with_glyph(mut self, glyph: Handle<Image>) -> Self {
+        if let MenuItem::KeyBinding(_, _, _, value) = self.inner_mut() {
+            value.glyph = Some(glyph);
+        }
+        self
+    }
+
+    /// The seconds a `MenuItem::KeyBinding` waits for an input
+    pub fn with_capture_timeout(mut self, timeout: f32) -> Self {
+        if let MenuItem::KeyBinding(_, _, _, value) = self.inner_mut() {
+            value.timeout = timeout;
+        }
+        self
+    }
+
     pub fn label(s: impl Into<WidgetLabel>) -> Self {
         MenuItem::Label(s.into(), MenuIcon::None)
     }
@@ -135,9 +653,15 @@ where
         match self {
             MenuItem::Screen(a, _, b) => MenuItem::Screen(a, icon, b),
             MenuItem::Action(a, _, b) => MenuItem::Action(a, icon, b),
+            MenuItem::Slider(a, _, b, c) => MenuItem::Slider(a, icon, b, c),
+            MenuItem::TextInput(a, _, b, c) => MenuItem::TextInput(a, icon, b, c),
+            MenuItem::KeyBinding(a, _, b, c) => MenuItem::KeyBinding(a, icon, b, c),
             MenuItem::Label(a, _) => MenuItem::Label(a, icon),
             MenuItem::Headline(a, _) => MenuItem::Headline(a, icon),
             MenuItem::Image(a, b) => MenuItem::Image(a, b),
+            MenuItem::Described(a, b) => MenuItem::Described(Box::new(a.with_icon(icon)), b),
+            MenuItem::Confirmed(a, b) => MenuItem::Confirmed(Box::new(a.with_icon(icon)), b),
+            MenuItem::Disabled(a) => MenuItem::Disabled(Box::new(a.with_icon(icon))),
         }
     }
 
@@ -149,22 +673,172 @@ where
         }
     }
 
+    /// A disabled `MenuItem::Screen` or `MenuItem::Action` is rendered with the
+    /// `disabled` style and can neither be focused nor selected.
+    /// Other items are returned unchanged.
+    pub fn disabled(self, disabled: bool) -> Self {
+        if !matches!(self.inner(), MenuItem::Screen(..) | MenuItem::Action(..)) {
+            return self;
+        }
+        match (self, disabled) {
+            (MenuItem::Disabled(item), false) => *item,
+            (MenuItem::Described(item, description), false) => {
+                MenuItem::Described(Box::new(item.disabled(false)), description)
+            }
+            (MenuItem::Confirmed(item, confirmation), false) => {
+                MenuItem::Confirmed(Box::new(item.disabled(false)), confirmation)
+            }
+            (item, true) if !item.is_disabled() => MenuItem::Disabled(Box::new(item)),
+            (item, _) => item,
+        }
+    }
+
+    /// Explain the item. The description is shown in the `description` panel
+    /// of the `Stylesheet` while the item is focused. Without a panel, it is
+    /// shown as a tooltip while the mouse is over the item.
+    pub fn with_description(self, text: impl Into<WidgetLabel>) -> Self {
+        self.map_description(|description| description.text = text.into())
+    }
+
+    /// An image that is shown with the description, e.g. a preview of a setting
+    pub fn with_description_image(self, image: Handle<Image>) -> Self {
+        self.map_description(|description| description.image = Some(image))
+    }
+
+    fn map_description(mut self, f: impl FnOnce(&mut ItemDescription)) -> Self {
+        match self.description_mut() {
+            Some(description) => f(description),
+            None => {
+                let mut description = ItemDescription {
+                    text: WidgetLabel::default(),
+                    image: None,
+                };
+                f(&mut description);
+                self = MenuItem::Described(Box::new(self), description);
+            }
+        }
+        self
+    }
+
+    /// Ask for confirmation before the action of a `MenuItem::Action` is
+    /// handled. A dialog with `Yes` and `No` buttons is shown on top of the
+    /// menu, and [`crate::ActionTrait::handle`] is only called for `Yes`.
+    /// Other items are returned unchanged.
+    pub fn with_confirmation(mut self, confirmation: Confirmation) -> Self {
+        if !matches!(self.inner(), MenuItem::Action(..)) {
+            return self;
+        }
+        match self.confirmation_mut() {
+            Some(existing) => *existing = confirmation,
+            None => self = MenuItem::Confirmed(Box::new(self), confirmation),
+        }
+        self
+    }
+
+    /// The item without its description, confirmation and disabled state
+    pub(crate) fn inner(&self) -> &MenuItem<S> {
+        match self {
+            MenuItem::Described(item, _)
+            | MenuItem::Confirmed(item, _)
+            | MenuItem::Disabled(item) => item.inner(),
+            item => item,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut MenuItem<S> {
+        match self {
+            MenuItem::Described(item, _)
+            | MenuItem::Confirmed(item, _)
+            | MenuItem::Disabled(item) => item.inner_mut(),
+            item => item,
+        }
+    }
+
+    pub(crate) fn description(&self) -> Option<&ItemDescription> {
+        match self {
+            MenuItem::Described(_, description) => Some(description),
+            MenuItem::Confirmed(item, _) | MenuItem::Disabled(item) => item.description(),
+            _ => None,
+        }
+    }
+
+    fn description_mut(&mut self) -> Option<&mut ItemDescription> {
+        match self {
+            MenuItem::Described(_, description) => Some(description),
+            MenuItem::Confirmed(item, _) | MenuItem::Disabled(item) => item.description_mut(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn confirmation(&self) -> Option<&Confirmation> {
+        match self {
+            MenuItem::Confirmed(_, confirmation) => Some(confirmation),
+            MenuItem::Described(item, _) | MenuItem::Disabled(item) => item.confirmation(),
+            _ => None,
+        }
+    }
+
+    fn confirmation_mut(&mut self) -> Option<&mut Confirmation> {
+        match self {
+            MenuItem::Confirmed(_, confirmation) => Some(confirmation),
+            MenuItem::Described(item, _) | MenuItem::Disabled(item) => item.confirmation_mut(),
+            _ => None,
+        }
+    }
+
     pub(crate) fn as_selection(&self) -> MenuSelection<S> {
         match self {
             MenuItem::Screen(_, _, a) => MenuSelection::Screen(*a),
             MenuItem::Action(_, _, a) => MenuSelection::Action(*a),
+            MenuItem::Slider(_, _, a, _) => MenuSelection::Slider(*a),
+            MenuItem::TextInput(_, _, a, _) => MenuSelection::Action(*a),
+            MenuItem::KeyBinding(_, _, a, _) => MenuSelection::Action(*a),
             MenuItem::Label(_, _) => MenuSelection::None,
             MenuItem::Headline(_, _) => MenuSelection::None,
             MenuItem::Image(_, _) => MenuSelection::None,
+            MenuItem::Described(a, _) | MenuItem::Confirmed(a, _) | MenuItem::Disabled(a) => {
+                a.as_selection()
+            }
         }
     }
 
     pub(crate) fn is_selectable(&self) -> bool {
         !matches!(
-            self,
+            self.inner(),
             MenuItem::Label(_, _) | MenuItem::Headline(_, _) | MenuItem::Image(_, _)
         )
     }
+
+    /// Resolve a `WidgetLabel::Localized` label of the item
+    pub(crate) fn localize(&mut self, localization: &MenuLocalization) {
+        match self {
+            MenuItem::Screen(label, ..)
+            | MenuItem::Action(label, ..)
+            | MenuItem::Slider(label, ..)
+            | MenuItem::TextInput(label, ..)
+            | MenuItem::KeyBinding(label, ..)
+            | MenuItem::Label(label, _)
+            | MenuItem::Headline(label, _) => label.localize(localization),
+            MenuItem::Image(..) => (),
+            MenuItem::Described(item, description) => {
+                item.localize(localization);
+                description.text.localize(localization);
+            }
+            MenuItem::Confirmed(item, confirmation) => {
+                item.localize(localization);
+                confirmation.localize(localization);
+            }
+            MenuItem::Disabled(item) => item.localize(localization),
+        }
+    }
+
+    pub(crate) fn is_disabled(&self) -> bool {
+        match self {
+            MenuItem::Disabled(_) => true,
+            MenuItem::Described(item, _) | MenuItem::Confirmed(item, _) => item.is_disabled(),
+            _ => false,
+        }
+    }
 }
 
 impl<S> std::fmt::Debug for MenuItem<S>
@@ -175,10 +849,352 @@ where
         match self {
             Self::Screen(arg0, _, _) => f.debug_tuple("Screen").field(&arg0.debug_text()).finish(),
             Self::Action(arg0, _, _) => f.debug_tuple("Action").field(&arg0.debug_text()).finish(),
+            Self::Slider(arg0, _, _, arg1) => f
+                .debug_tuple("Slider")
+                .field(&arg0.debug_text())
+                .field(&arg1.value)
+                .finish(),
+            Self::TextInput(arg0, _, _, arg1) => f
+                .debug_tuple("TextInput")
+                .field(&arg0.debug_text())
+                .field(&arg1.text)
+                .finish(),
+            Self::KeyBinding(arg0, _, _, arg1) => f
+                .debug_tuple("KeyBinding")
+                .field(&arg0.debug_text())
+                .field(&arg1.input)
+                .finish(),
             Self::Label(arg0, _) => f.debug_tuple("Label").field(&arg0.debug_text()).finish(),
             Self::Headline(arg0, _) => f.debug_tuple("Headline").field(&arg0.debug_text()).finish(),
             Self::Image(arg0, _) => f.debug_tuple("Image").field(&arg0).finish(),
+            Self::Described(arg0, arg1) => f
+                .debug_tuple("Described")
+                .field(arg0)
+                .field(&arg1.text.debug_text())
+                .finish(),
+            Self::Confirmed(arg0, arg1) => f
+                .debug_tuple("Confirmed")
+                .field(arg0)
+                .field(&arg1.text.debug_text())
+                .finish(),
+            Self::Disabled(arg0) => f.debug_tuple("Disabled").field(arg0).finish(),
+        }
+    }
+}
+
+/// The explanation of a `MenuItem`, see `MenuItem::with_description`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemDescription {
+    pub text: WidgetLabel,
+    pub image: Option<Handle<Image>>,
+}
+
+/// The buttons of a confirmation dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ConfirmationAnswer {
+    Yes,
+    #[default]
+    No,
+}
+
+/// The texts of the dialog that confirms a `MenuItem::Action`,
+/// see `MenuItem::with_confirmation`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confirmation {
+    /// The question, e.g. "Are you sure?"
+    pub text: WidgetLabel,
+    pub yes: WidgetLabel,
+    pub no: WidgetLabel,
+    /// The button that is focused when the dialog opens
+    pub focus: ConfirmationAnswer,
+}
+
+impl Confirmation {
+    pub fn new(text: impl Into<WidgetLabel>) -> Self {
+        Self {
+            text: text.into(),
+            yes: "Yes".into(),
+            no: "No".into(),
+            focus: ConfirmationAnswer::No,
+        }
+    }
+
+    pub fn with_yes(mut self, yes: impl Into<WidgetLabel>) -> Self {
+        self.yes = yes.into();
+        self
+    }
+
+    pub fn with_no(mut self, no: impl Into<WidgetLabel>) -> Self {
+        self.no = no.into();
+        self
+    }
+
+    pub fn with_focus(mut self, focus: ConfirmationAnswer) -> Self {
+        self.focus = focus;
+        self
+    }
+
+    pub(crate) fn localize(&mut self, localization: &MenuLocalization) {
+        self.text.localize(localization);
+        self.yes.localize(localization);
+        self.no.localize(localization);
+    }
+}
+
+/// The value of a `MenuItem::Slider` and its range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliderValue {
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+}
+
+impl SliderValue {
+    /// The position of the value within the range, from `0.0` to `1.0`
+    pub fn fraction(&self) -> f32 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+        ((self.value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+
+    /// Snap a value onto the steps of the range
+    pub fn snapped(&self, value: f32) -> f32 {
+        let value = value.clamp(self.min, self.max);
+        if self.step <= 0.0 {
+            return value;
+        }
+        let steps = ((value - self.min) / self.step).round();
+        (self.min + steps * self.step).clamp(self.min, self.max)
+    }
+
+    /// The value at a given position (`0.0` to `1.0`) of the range
+    pub fn value_at(&self, fraction: f32) -> f32 {
+        self.snapped(self.min + fraction.clamp(0.0, 1.0) * (self.max - self.min))
+    }
+
+    /// The value after applying one step of `adjustment`
+    pub fn adjusted(&self, adjustment: Adjustment) -> f32 {
+        match adjustment {
+            Adjustment::Increment => self.snapped(self.value + self.step),
+            Adjustment::Decrement => self.snapped(self.value - self.step),
+        }
+    }
+
+    /// The value as text, with as many decimals as the step requires
+    pub fn display_text(&self) -> String {
+        let mut decimals = 0;
+        let mut step = self.step.abs();
+        while decimals < 3 && (step - step.round()).abs() > f32::EPSILON * 16.0 {
+            step *= 10.0;
+            decimals += 1;
         }
+        format!("{:.*}", decimals, self.value)
+    }
+}
+
+/// The characters that can be entered into a `MenuItem::TextInput`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CharacterFilter {
+    /// Any printable character
+    #[default]
+    Any,
+    Alphanumeric,
+    /// Alphanumeric characters and spaces, e.g. for player names
+    AlphanumericAndSpace,
+    Numeric,
+    /// Only the characters in the given string
+    OneOf(&'static str),
+}
+
+impl CharacterFilter {
+    pub fn allows(&self, c: char) -> bool {
+        if c.is_control() {
+            return false;
+        }
+        match self {
+            CharacterFilter::Any => true,
+            CharacterFilter::Alphanumeric => c.is_alphanumeric(),
+            CharacterFilter::AlphanumericAndSpace => c.is_alphanumeric() || c == ' ',
+            CharacterFilter::Numeric => c.is_numeric(),
+            CharacterFilter::OneOf(chars) => chars.contains(c),
+        }
+    }
+}
+
+/// The value of a `MenuItem::TextInput`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextInputValue {
+    pub text: String,
+    pub placeholder: String,
+    pub max_length: Option<usize>,
+    pub filter: CharacterFilter,
+}
+
+impl TextInputValue {
+    /// Append a character if it passes the filter and the maximum length.
+    /// Returns whether the text changed.
+    pub fn insert(&mut self, c: char) -> bool {
+        let full = self
+            .max_length
+            .is_some_and(|max| self.text.chars().count() >= max);
+        if full || !self.filter.allows(c) {
+            return false;
+        }
+        self.text.push(c);
+        true
+    }
+
+    /// Remove the last character. Returns whether the text changed.
+    pub fn delete(&mut self) -> bool {
+        self.text.pop().is_some()
+    }
+}
+
+/// The value of a `MenuItem::KeyBinding`
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBindingValue {
+    /// The bound input, `None` if nothing is bound
+    pub input: Option<BoundInput>,
+    pub glyph: Option<Handle<Image>>,
+    /// The seconds to wait for an input before the capture is cancelled
+    pub timeout: f32,
+}
+
+impl Default for KeyBindingValue {
+    fn default() -> Self {
+        Self {
+            input: None,
+            glyph: None,
+            timeout: 5.0,
+        }
+    }
+}
+
+/// The `MenuItem::KeyBinding` that is waiting for an input and for how long
+/// it has been waiting. While capturing, inputs don't navigate the menu.
+#[derive(Default)]
+pub struct BindingCapture {
+    pub target: Option<(WidgetId, usize)>,
+    pub elapsed: f32,
+    /// Set in the frame in which an input was captured, so that a mouse
+    /// click that was bound doesn't also press a button
+    pub(crate) captured: bool,
+}
+
+impl BindingCapture {
+    pub(crate) fn start(&mut self, target: (WidgetId, usize)) {
+        self.target = Some(target);
+        self.elapsed = 0.0;
+    }
+}
+
+/// The `MenuItem::TextInput` that currently receives the keyboard input.
+/// While a text input is edited, the keyboard doesn't navigate the menu.
+#[derive(Default)]
+pub struct TextEditing {
+    pub target: Option<(WidgetId, usize)>,
+    /// The text from before editing, which `Escape` restores
+    pub original: String,
+}
+
+impl TextEditing {
+    pub(crate) fn start(&mut self, target: (WidgetId, usize), original: String) {
+        self.target = Some(target);
+        self.original = original;
+    }
+}
+
+/// The resources of the `QuickMenuPlugin` of a screen type: its assets and
+/// the selections, scroll offsets, text editing and key binding capture of
+/// its `MenuState` resource. Each screen type has its own, so that plugins
+/// of different screen types don't overwrite each other.
+#[derive(Resource)]
+pub struct MenuResources<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub assets: MenuAssets,
+    pub selections: Selections,
+    pub scrolls: ScrollOffsets,
+    pub editing: TextEditing,
+    pub capture: BindingCapture,
+    screen: std::marker::PhantomData<S>,
+}
+
+impl<S> MenuResources<S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub(crate) fn new(assets: MenuAssets) -> Self {
+        Self {
+            assets,
+            selections: Selections::default(),
+            scrolls: ScrollOffsets::default(),
+            editing: TextEditing::default(),
+            capture: BindingCapture::default(),
+            screen: Default::default(),
+        }
+    }
+}
+
+/// Identifies a menu among the menus of all screen types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MenuId {
+    /// The `MenuState` resource of a screen type
+    Resource(std::any::TypeId),
+    /// A `QuickMenu` instance
+    Instance(Entity),
+}
+
+impl MenuId {
+    /// The `MenuState` resource of the screen type `S`
+    pub fn resource<S: 'static>() -> Self {
+        Self::Resource(std::any::TypeId::of::<S>())
+    }
+
+    /// The menu of an owner, `None` for the resource
+    pub(crate) fn of<S: 'static>(owner: Option<Entity>) -> Self {
+        owner.map_or(Self::resource::<S>(), Self::Instance)
+    }
+}
+
+/// The open menus of all screen types, in the order in which they were
+/// opened. Only the focused menu, the one that was opened or clicked last,
+/// receives the navigation events, so that an input only moves one menu.
+/// Menus with an `input_device` receive the input of their device even
+/// without the focus.
+#[derive(Resource, Debug, Default)]
+pub struct MenuFocus {
+    menus: Vec<MenuId>,
+}
+
+impl MenuFocus {
+    /// The menu that receives the navigation events
+    pub fn focused(&self) -> Option<MenuId> {
+        self.menus.last().copied()
+    }
+
+    /// Move the focus to an open menu
+    pub fn focus(&mut self, menu: MenuId) {
+        if self.menus.contains(&menu) {
+            self.open(menu);
+        }
+    }
+
+    /// Whether no menu is open
+    pub fn is_empty(&self) -> bool {
+        self.menus.is_empty()
+    }
+
+    pub(crate) fn open(&mut self, menu: MenuId) {
+        self.close(menu);
+        self.menus.push(menu);
+    }
+
+    pub(crate) fn close(&mut self, menu: MenuId) {
+        self.menus.retain(|open| *open != menu);
     }
 }
 
@@ -189,6 +1205,8 @@ where
 {
     Action(S::Action),
     Screen(S),
+    /// A `MenuItem::Slider`, kept apart from an action item with the same action
+    Slider(S::Action),
     None,
 }
 
@@ -200,6 +1218,7 @@ where
         match self {
             Self::Action(arg0) => Self::Action(*arg0),
             Self::Screen(arg0) => Self::Screen(*arg0),
+            Self::Slider(arg0) => Self::Slider(*arg0),
             Self::None => Self::None,
         }
     }
@@ -213,6 +1232,7 @@ where
         match self {
             Self::Action(arg0) => f.debug_tuple("Action").field(&arg0).finish(),
             Self::Screen(arg0) => f.debug_tuple("Screen").field(&arg0).finish(),
+            Self::Slider(arg0) => f.debug_tuple("Slider").field(&arg0).finish(),
             Self::None => f.debug_tuple("None").finish(),
         }
     }
@@ -226,6 +1246,7 @@ where
         match (self, other) {
             (MenuSelection::Action(a1), MenuSelection::Action(a2)) => a1 == a2,
             (MenuSelection::Screen(s1), MenuSelection::Screen(s2)) => s1 == s2,
+            (MenuSelection::Slider(a1), MenuSelection::Slider(a2)) => a1 == a2,
             (MenuSelection::None, MenuSelection::None) => true,
             _ => false,
         }
@@ -235,6 +1256,7 @@ where
 /// The library comes with some pre-defined icons for several screens.
 /// Custom icons can be used with `MenuIcon::Other` or by overriding
 /// the existing ones via `MenuOptions`
+#[derive(Clone, PartialEq)]
 pub enum MenuIcon {
     None,
     Checked,
@@ -264,7 +1286,7 @@ impl MenuIcon {
 }
 
 /// Simplified Rich-Text that assumes the default font
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct RichTextEntry {
     pub text: String,
     pub color: Option<Color>,
@@ -290,16 +1312,44 @@ impl RichTextEntry {
 }
 
 /// Abstraction over text for buttons and labels
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum WidgetLabel {
     PlainText(String),
     RichText(Vec<RichTextEntry>),
+    /// A message key and its arguments, resolved via the `MenuLocalization`
+    /// when the label is rendered
+    Localized(String, Vec<(String, LocalizationArg)>),
 }
 
 impl WidgetLabel {
+    /// A label that is resolved via the `MenuLocalization`
+    pub fn localized(key: impl Into<String>) -> Self {
+        Self::Localized(key.into(), Vec::new())
+    }
+
+    /// Add an argument to a `WidgetLabel::Localized`
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<LocalizationArg>) -> Self {
+        if let Self::Localized(_, args) = &mut self {
+            args.push((name.into(), value.into()));
+        }
+        self
+    }
+
+    /// Resolve a `WidgetLabel::Localized` into plain text. Missing messages
+    /// show their key.
+    pub(crate) fn localize(&mut self, localization: &MenuLocalization) {
+        if let Self::Localized(key, args) = self {
+            let text = localization
+                .localize(key, args)
+                .unwrap_or_else(|| key.clone());
+            *self = Self::PlainText(text);
+        }
+    }
+
     pub fn bundle(&self, default_style: &TextStyle) -> TextBundle {
         match self {
             Self::PlainText(text) => TextBundle::from_section(text, default_style.clone()),
+            Self::Localized(key, _) => TextBundle::from_section(key, default_style.clone()),
             Self::RichText(entries) => TextBundle::from_sections(entries.iter().map(|entry| {
                 TextSection {
                     value: entry.text.clone(),
@@ -320,6 +1370,7 @@ impl WidgetLabel {
     pub fn debug_text(&self) -> String {
         match self {
             Self::PlainText(text) => text.clone(),
+            Self::Localized(key, _) => key.clone(),
             Self::RichText(entries) => {
                 let mut output = String::new();
                 for entry in entries {
@@ -367,8 +1418,12 @@ impl<const N: usize> From<[RichTextEntry; N]> for WidgetLabel {
 }
 
 /// Changing these `MenuOptions` allows overriding the provided
-/// images and fonts. Use [`crate::QuickMenuPlugin::with_options`] to do this.
-#[derive(Resource, Default, Clone, Copy)]
+/// images, fonts and input bindings. Use [`crate::QuickMenuPlugin::with_options`] to do this.
+///
+/// The fonts and icons belong to the menus of the plugin's screen type. The
+/// `input_map` and the `sounds` are shared by the menus of all screen types,
+/// so they are taken from the first plugin that is added.
+#[derive(Default, Clone)]
 pub struct MenuOptions {
     pub font: Option<&'static str>,
     pub icon_checked: Option<&'static str>,
@@ -378,9 +1433,25 @@ pub struct MenuOptions {
     pub icon_sound: Option<&'static str>,
     pub icon_players: Option<&'static str>,
     pub icon_settings: Option<&'static str>,
+    /// The mapping from keys, buttons and axes to `NavigationEvent`s
+    pub input_map: MenuInputMap,
+    /// The sounds that are played during navigation, see `MenuSounds`
+    pub sounds: MenuSounds,
+}
+
+/// The paths of the sounds that are played during navigation. They are only
+/// played with the `audio` feature. Sounds that are `None` are not played.
+#[derive(Debug, Clone, Default)]
+pub struct MenuSounds {
+    pub focus: Option<&'static str>,
+    pub select: Option<&'static str>,
+    pub back: Option<&'static str>,
+    pub error: Option<&'static str>,
+    pub value_change: Option<&'static str>,
+    /// The volume of all sounds, defaults to `1.0`
+    pub volume: Option<f32>,
 }
 
-#[derive(Resource)]
 pub struct MenuAssets {
     pub font: Handle<Font>,
     pub icon_checked: Handle<Image>,
@@ -392,9 +1463,8 @@ pub struct MenuAssets {
     pub icon_settings: Handle<Image>,
 }
 
-impl FromWorld for MenuAssets {
-    fn from_world(world: &mut World) -> Self {
-        let options = *(world.get_resource::<MenuOptions>().unwrap());
+impl MenuAssets {
+    pub(crate) fn new(options: &MenuOptions, world: &mut World) -> Self {
         let font = {
             let assets = world.get_resource::<AssetServer>().unwrap();
             let font = match options.font {
@@ -557,3 +1627,100 @@ impl<T: Into<Cow<'static, str>>> From<T> for WidgetId {
         WidgetId::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slider_snaps_onto_steps() {
+        let slider = SliderValue {
+            value: 0.5,
+            min: 0.0,
+            max: 1.0,
+            step: 0.25,
+        };
+        assert_eq!(slider.snapped(0.6), 0.5);
+        assert_eq!(slider.snapped(0.9), 1.0);
+        assert_eq!(slider.snapped(-3.0), 0.0);
+        assert_eq!(slider.value_at(0.3), 0.25);
+        assert_eq!(slider.adjusted(Adjustment::Increment), 0.75);
+        assert_eq!(slider.adjusted(Adjustment::Decrement), 0.25);
+        assert_eq!(slider.fraction(), 0.5);
+        assert_eq!(slider.display_text(), "0.50");
+
+        let at_max = SliderValue {
+            value: 1.0,
+            ..slider
+        };
+        assert_eq!(at_max.adjusted(Adjustment::Increment), 1.0);
+        let stepless = SliderValue {
+            step: 0.0,
+            ..slider
+        };
+        assert_eq!(stepless.snapped(0.33), 0.33);
+        assert_eq!(
+            SliderValue {
+                value: 3.0,
+                min: 0.0,
+                max: 10.0,
+                step: 1.0
+            }
+            .display_text(),
+            "3"
+        );
+    }
+
+    #[test]
+    fn text_input_applies_filter_and_max_length() {
+        let mut value = TextInputValue {
+            max_length: Some(3),
+            filter: CharacterFilter::AlphanumericAndSpace,
+            ..Default::default()
+        };
+        assert!(value.insert('a'));
+        assert!(!value.insert('!'));
+        assert!(value.insert(' '));
+        assert!(!value.insert('\n'));
+        assert!(value.insert('7'));
+        assert!(!value.insert('b'));
+        assert_eq!(value.text, "a 7");
+        assert!(value.delete());
+        assert_eq!(value.text, "a ");
+
+        assert!(CharacterFilter::Numeric.allows('4'));
+        assert!(!CharacterFilter::Numeric.allows('x'));
+        assert!(CharacterFilter::OneOf("xy").allows('y'));
+        assert!(!CharacterFilter::OneOf("xy").allows('z'));
+        assert!(!CharacterFilter::Any.allows('\u{7}'));
+    }
+
+    #[test]
+    fn scroll_offset_follows_the_focus() {
+        let mut scroll = ScrollOffset::default();
+        // 10 items, 4 visible rows, selectable from position 1 to 9
+        scroll.update(4, 10, Some(1), Some(1), Some(9));
+        assert_eq!((scroll.offset, scroll.max_offset), (0, 6));
+        scroll.update(4, 10, Some(5), Some(1), Some(9));
+        assert_eq!(scroll.offset, 2);
+        scroll.update(4, 10, Some(3), Some(1), Some(9));
+        assert_eq!(scroll.offset, 2);
+        scroll.update(4, 10, Some(9), Some(1), Some(9));
+        assert_eq!(scroll.offset, 6);
+        scroll.update(4, 10, Some(1), Some(1), Some(9));
+        assert_eq!(scroll.offset, 0);
+
+        // The mouse wheel scrolls away from the focus until the focus moves
+        scroll.scroll_by(10);
+        assert_eq!(scroll.offset, 6);
+        scroll.update(4, 10, Some(1), Some(1), Some(9));
+        assert_eq!(scroll.offset, 6);
+        scroll.scroll_by(-10);
+        assert_eq!(scroll.offset, 0);
+
+        // A shrinking menu clamps the offset
+        scroll.scroll_by(6);
+        scroll.update(4, 6, Some(1), Some(1), Some(5));
+        assert_eq!((scroll.offset, scroll.max_offset), (2, 2));
+    }
+}
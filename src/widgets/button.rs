@@ -13,6 +13,7 @@ where
     menu_identifier: (WidgetId, usize),
     selection: &'a MenuSelection<S>,
     selected: bool,
+    disabled: bool,
 }
 
 impl<'a, S> ButtonWidget<'a, S>
@@ -25,6 +26,7 @@ where
         menu_identifier: (WidgetId, usize),
         selection: &'a MenuSelection<S>,
         selected: bool,
+        disabled: bool,
     ) -> Self {
         Self {
             text,
@@ -32,6 +34,7 @@ where
             menu_identifier,
             selection,
             selected,
+            disabled,
         }
     }
 }
@@ -49,9 +52,12 @@ where
             menu_identifier,
             selection,
             selected,
+            disabled,
         } = self;
 
-        let (bg, fg) = if selected {
+        let (bg, fg) = if disabled {
+            (style.disabled.bg, style.disabled.fg)
+        } else if selected {
             (style.selected.bg, style.selected.fg)
         } else {
             (style.normal.bg, style.normal.fg)
@@ -80,6 +86,7 @@ where
                 selection: selection.clone(),
                 menu_identifier,
                 selected,
+                disabled,
             })
             .with_children(|parent| {
                 parent.spawn(text.bundle(&text_style));
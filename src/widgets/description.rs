@@ -0,0 +1,58 @@
+use super::Widget;
+use crate::types::{ItemDescription, MenuAssets};
+use bevy::prelude::*;
+
+/// The image and text of an item description, shown in the description
+/// panel or in a tooltip
+pub struct DescriptionWidget<'a> {
+    description: &'a ItemDescription,
+    text: Color,
+    font_size: f32,
+    image_size: Val,
+}
+
+impl<'a> DescriptionWidget<'a> {
+    pub fn new(
+        description: &'a ItemDescription,
+        text: Color,
+        font_size: f32,
+        image_size: Val,
+    ) -> Self {
+        Self {
+            description,
+            text,
+            font_size,
+            image_size,
+        }
+    }
+}
+
+impl<'a> Widget for DescriptionWidget<'a> {
+    fn build(self, parent: &mut ChildBuilder, assets: &MenuAssets) {
+        let DescriptionWidget {
+            description,
+            text,
+            font_size,
+            image_size,
+        } = self;
+
+        if let Some(image) = &description.image {
+            parent.spawn(ImageBundle {
+                style: Style {
+                    width: image_size,
+                    height: image_size,
+                    margin: UiRect::all(Val::Px(5.0)),
+                    flex_shrink: 0.0,
+                    ..default()
+                },
+                image: image.clone().into(),
+                ..default()
+            });
+        }
+        parent.spawn(description.text.bundle(&TextStyle {
+            font: assets.font.clone(),
+            font_size,
+            color: text,
+        }));
+    }
+}
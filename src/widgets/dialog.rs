@@ -0,0 +1,89 @@
+use super::Widget;
+use crate::style::Stylesheet;
+use crate::types::{ConfirmationAnswer, DialogButtonComponent, MenuAssets, RenderedDialog};
+use bevy::prelude::*;
+
+/// The confirmation dialog: the question and a `Yes` and `No` button
+pub struct DialogWidget<'a> {
+    dialog: &'a RenderedDialog,
+    stylesheet: &'a Stylesheet,
+}
+
+impl<'a> DialogWidget<'a> {
+    pub(crate) fn new(dialog: &'a RenderedDialog, stylesheet: &'a Stylesheet) -> Self {
+        Self { dialog, stylesheet }
+    }
+}
+
+impl<'a> Widget for DialogWidget<'a> {
+    fn build(self, parent: &mut ChildBuilder, assets: &MenuAssets) {
+        let DialogWidget { dialog, stylesheet } = self;
+        let style = &stylesheet.dialog;
+        let button_style = &stylesheet.button;
+
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: style.padding,
+                    ..default()
+                },
+                background_color: BackgroundColor(style.background),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn(dialog.confirmation.text.bundle(&TextStyle {
+                    font: assets.font.clone(),
+                    font_size: style.font_size,
+                    color: style.text,
+                }));
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            margin: UiRect::top(Val::Px(stylesheet.vertical_spacing)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        for (answer, label) in [
+                            (ConfirmationAnswer::Yes, &dialog.confirmation.yes),
+                            (ConfirmationAnswer::No, &dialog.confirmation.no),
+                        ] {
+                            let focused = dialog.focus == answer;
+                            let (bg, fg) = if focused {
+                                (button_style.selected.bg, button_style.selected.fg)
+                            } else {
+                                (button_style.normal.bg, button_style.normal.fg)
+                            };
+                            parent
+                                .spawn(ButtonBundle {
+                                    style: Style {
+                                        margin: button_style.margin,
+                                        padding: button_style.padding,
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..default()
+                                    },
+                                    background_color: BackgroundColor(bg),
+                                    ..default()
+                                })
+                                .insert(DialogButtonComponent {
+                                    answer,
+                                    style: button_style.clone(),
+                                    focused,
+                                })
+                                .with_children(|parent| {
+                                    parent.spawn(label.bundle(&TextStyle {
+                                        font: assets.font.clone(),
+                                        font_size: button_style.size,
+                                        color: fg,
+                                    }));
+                                });
+                        }
+                    });
+            });
+    }
+}
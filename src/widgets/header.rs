@@ -0,0 +1,130 @@
+use super::Widget;
+use crate::style::{Breadcrumb, Stylesheet};
+use crate::types::{BackButtonComponent, BreadcrumbComponent, MenuAssets, RenderedHeader};
+use crate::ScreenTrait;
+use bevy::prelude::*;
+
+/// The header above the screens: the titles of the stacked screens and,
+/// in the `SingleScreen` layout, a button that goes back
+pub struct HeaderWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    header: &'a RenderedHeader<S>,
+    stylesheet: &'a Stylesheet,
+}
+
+impl<'a, S> HeaderWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    pub(crate) fn new(header: &'a RenderedHeader<S>, stylesheet: &'a Stylesheet) -> Self {
+        Self { header, stylesheet }
+    }
+
+    fn build_breadcrumb(
+        &self,
+        breadcrumb: &Breadcrumb,
+        parent: &mut ChildBuilder,
+        assets: &MenuAssets,
+    ) {
+        let style = &breadcrumb.style;
+        let text_style = |color: Color| TextStyle {
+            font: assets.font.clone(),
+            font_size: style.size,
+            color,
+        };
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|parent| {
+                for (index, (title, selection)) in self.header.crumbs.iter().enumerate() {
+                    if index > 0 {
+                        parent.spawn(
+                            TextBundle::from_section(
+                                &breadcrumb.separator,
+                                text_style(style.normal.fg),
+                            )
+                            .with_style(Style {
+                                margin: style.margin,
+                                ..default()
+                            }),
+                        );
+                    }
+                    let (bg, fg) = match selection {
+                        Some(_) => (style.normal.bg, style.normal.fg),
+                        None => (style.selected.bg, style.selected.fg),
+                    };
+                    let mut crumb = parent.spawn(ButtonBundle {
+                        style: Style {
+                            margin: style.margin,
+                            padding: style.padding,
+                            ..default()
+                        },
+                        background_color: BackgroundColor(bg),
+                        ..default()
+                    });
+                    if let Some(selection) = selection {
+                        crumb.insert(BreadcrumbComponent {
+                            selection: selection.clone(),
+                            style: style.clone(),
+                        });
+                    }
+                    crumb.with_children(|parent| {
+                        parent.spawn(title.bundle(&text_style(fg)));
+                    });
+                }
+            });
+    }
+}
+
+impl<'a, S> Widget for HeaderWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    fn build(self, parent: &mut ChildBuilder, assets: &MenuAssets) {
+        if let Some(breadcrumb) = self
+            .stylesheet
+            .breadcrumb
+            .as_ref()
+            .filter(|_| !self.header.crumbs.is_empty())
+        {
+            self.build_breadcrumb(breadcrumb, parent, assets);
+        }
+
+        if let Some(back) = &self.header.back {
+            let style = &self.stylesheet.button;
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        margin: style.margin,
+                        padding: style.padding,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: BackgroundColor(style.normal.bg),
+                    ..default()
+                })
+                .insert(BackButtonComponent {
+                    style: style.clone(),
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        back,
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: style.size,
+                            color: style.normal.fg,
+                        },
+                    ));
+                });
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use super::Widget;
+use crate::style::{KeyBindingStyle, StyleEntry};
+use crate::types::{
+    ButtonComponent, CapturingBinding, KeyBindingValue, MenuAssets, WidgetId, WidgetLabel,
+};
+use crate::{ActionTrait, MenuSelection, ScreenTrait};
+use bevy::prelude::*;
+
+pub struct KeyBindingWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    text: &'a WidgetLabel,
+    style: &'a StyleEntry,
+    binding_style: &'a KeyBindingStyle,
+    menu_identifier: (WidgetId, usize),
+    selection: &'a MenuSelection<S>,
+    value: &'a KeyBindingValue,
+    selected: bool,
+    capturing: bool,
+    conflict: bool,
+}
+
+impl<'a, S> KeyBindingWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        text: &'a WidgetLabel,
+        style: &'a StyleEntry,
+        binding_style: &'a KeyBindingStyle,
+        menu_identifier: (WidgetId, usize),
+        selection: &'a MenuSelection<S>,
+        value: &'a KeyBindingValue,
+        selected: bool,
+        capturing: bool,
+        conflict: bool,
+    ) -> Self {
+        Self {
+            text,
+            style,
+            binding_style,
+            menu_identifier,
+            selection,
+            value,
+            selected,
+            capturing,
+            conflict,
+        }
+    }
+}
+
+impl<'a, A, S, State> Widget for KeyBindingWidget<'a, S>
+where
+    State: 'static,
+    A: ActionTrait<State = State> + 'static,
+    S: ScreenTrait<Action = A> + 'static,
+{
+    fn build(self, parent: &mut ChildBuilder, assets: &MenuAssets) {
+        let KeyBindingWidget {
+            text,
+            style,
+            binding_style,
+            menu_identifier,
+            selection,
+            value,
+            selected,
+            capturing,
+            conflict,
+        } = self;
+
+        let (bg, fg) = if selected {
+            (style.selected.bg, style.selected.fg)
+        } else {
+            (style.normal.bg, style.normal.fg)
+        };
+
+        let text_style = TextStyle {
+            font: assets.font.clone(),
+            font_size: style.size,
+            color: fg,
+        };
+
+        let field_background = if capturing {
+            binding_style.capturing
+        } else if conflict {
+            binding_style.conflict
+        } else {
+            binding_style.background
+        };
+
+        let mut button = parent.spawn(ButtonBundle {
+            style: Style {
+                margin: style.margin,
+                padding: style.padding,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: BackgroundColor(bg),
+            ..default()
+        });
+        button.insert(ButtonComponent {
+            style: style.clone(),
+            selection: selection.clone(),
+            menu_identifier,
+            selected,
+            disabled: false,
+        });
+        if capturing {
+            button.insert(CapturingBinding);
+        }
+        button.with_children(|parent| {
+            parent.spawn(text.bundle(&text_style));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        min_width: binding_style.width,
+                        margin: binding_style.margin,
+                        padding: binding_style.padding,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: BackgroundColor(field_background),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    let content = match (&value.input, &value.glyph) {
+                        _ if capturing => binding_style.prompt.clone(),
+                        (Some(_), Some(glyph)) => {
+                            parent.spawn(ImageBundle {
+                                style: Style {
+                                    width: binding_style.glyph_size,
+                                    height: binding_style.glyph_size,
+                                    ..default()
+                                },
+                                image: glyph.clone().into(),
+                                ..default()
+                            });
+                            return;
+                        }
+                        (Some(input), None) => input.label(),
+                        (None, _) => binding_style.unbound.clone(),
+                    };
+                    parent.spawn(TextBundle::from_section(
+                        content,
+                        TextStyle {
+                            color: binding_style.text,
+                            ..text_style.clone()
+                        },
+                    ));
+                });
+        });
+    }
+}
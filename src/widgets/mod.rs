@@ -1,9 +1,21 @@
 mod button;
+mod description;
+mod dialog;
+mod header;
+mod key_binding;
 mod label;
+mod slider;
+mod text_input;
 mod vertical_menu;
 mod widget;
 
 pub use button::ButtonWidget;
+pub use description::DescriptionWidget;
+pub use dialog::DialogWidget;
+pub use header::HeaderWidget;
+pub use key_binding::KeyBindingWidget;
 pub use label::LabelWidget;
-pub use vertical_menu::VerticalMenu;
+pub use slider::SliderWidget;
+pub use text_input::TextInputWidget;
+pub use vertical_menu::{ExistingMenu, VerticalMenu};
 pub use widget::Widget;
@@ -0,0 +1,135 @@
+use super::Widget;
+use crate::style::{SliderStyle, StyleEntry};
+use crate::types::{
+    ButtonComponent, MenuAssets, SliderComponent, SliderValue, WidgetId, WidgetLabel,
+};
+use crate::{ActionTrait, MenuSelection, ScreenTrait};
+use bevy::prelude::*;
+
+pub struct SliderWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    text: &'a WidgetLabel,
+    style: &'a StyleEntry,
+    slider_style: &'a SliderStyle,
+    menu_identifier: (WidgetId, usize),
+    selection: &'a MenuSelection<S>,
+    action: S::Action,
+    value: SliderValue,
+    selected: bool,
+}
+
+impl<'a, S> SliderWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        text: &'a WidgetLabel,
+        style: &'a StyleEntry,
+        slider_style: &'a SliderStyle,
+        menu_identifier: (WidgetId, usize),
+        selection: &'a MenuSelection<S>,
+        action: S::Action,
+        value: SliderValue,
+        selected: bool,
+    ) -> Self {
+        Self {
+            text,
+            style,
+            slider_style,
+            menu_identifier,
+            selection,
+            action,
+            value,
+            selected,
+        }
+    }
+}
+
+impl<'a, A, S, State> Widget for SliderWidget<'a, S>
+where
+    State: 'static,
+    A: ActionTrait<State = State> + 'static,
+    S: ScreenTrait<Action = A> + 'static,
+{
+    fn build(self, parent: &mut ChildBuilder, assets: &MenuAssets) {
+        let SliderWidget {
+            text,
+            style,
+            slider_style,
+            menu_identifier,
+            selection,
+            action,
+            value,
+            selected,
+        } = self;
+
+        let (bg, fg) = if selected {
+            (style.selected.bg, style.selected.fg)
+        } else {
+            (style.normal.bg, style.normal.fg)
+        };
+
+        let text_style = TextStyle {
+            font: assets.font.clone(),
+            font_size: style.size,
+            color: fg,
+        };
+
+        parent
+            .spawn(ButtonBundle {
+                style: Style {
+                    margin: style.margin,
+                    padding: style.padding,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(bg),
+                ..default()
+            })
+            .insert(ButtonComponent {
+                style: style.clone(),
+                selection: selection.clone(),
+                menu_identifier: menu_identifier.clone(),
+                selected,
+                disabled: false,
+            })
+            .with_children(|parent| {
+                parent.spawn(text.bundle(&text_style));
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: slider_style.width,
+                            height: slider_style.height,
+                            margin: slider_style.margin,
+                            ..default()
+                        },
+                        background_color: BackgroundColor(slider_style.track),
+                        ..default()
+                    })
+                    .insert(SliderComponent::<S> {
+                        action,
+                        value,
+                        menu_identifier,
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Percent(value.fraction() * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(slider_style.fill),
+                            ..default()
+                        });
+                    });
+                parent.spawn(TextBundle::from_section(
+                    value.display_text(),
+                    text_style.clone(),
+                ));
+            });
+    }
+}
@@ -0,0 +1,137 @@
+use super::Widget;
+use crate::style::{StyleEntry, TextInputStyle};
+use crate::types::{
+    ButtonComponent, EditingTextInput, MenuAssets, TextInputValue, WidgetId, WidgetLabel,
+};
+use crate::{ActionTrait, MenuSelection, ScreenTrait};
+use bevy::prelude::*;
+
+pub struct TextInputWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    text: &'a WidgetLabel,
+    style: &'a StyleEntry,
+    input_style: &'a TextInputStyle,
+    menu_identifier: (WidgetId, usize),
+    selection: &'a MenuSelection<S>,
+    value: &'a TextInputValue,
+    selected: bool,
+    editing: bool,
+}
+
+impl<'a, S> TextInputWidget<'a, S>
+where
+    S: ScreenTrait + 'static,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        text: &'a WidgetLabel,
+        style: &'a StyleEntry,
+        input_style: &'a TextInputStyle,
+        menu_identifier: (WidgetId, usize),
+        selection: &'a MenuSelection<S>,
+        value: &'a TextInputValue,
+        selected: bool,
+        editing: bool,
+    ) -> Self {
+        Self {
+            text,
+            style,
+            input_style,
+            menu_identifier,
+            selection,
+            value,
+            selected,
+            editing,
+        }
+    }
+}
+
+impl<'a, A, S, State> Widget for TextInputWidget<'a, S>
+where
+    State: 'static,
+    A: ActionTrait<State = State> + 'static,
+    S: ScreenTrait<Action = A> + 'static,
+{
+    fn build(self, parent: &mut ChildBuilder, assets: &MenuAssets) {
+        let TextInputWidget {
+            text,
+            style,
+            input_style,
+            menu_identifier,
+            selection,
+            value,
+            selected,
+            editing,
+        } = self;
+
+        let (bg, fg) = if selected {
+            (style.selected.bg, style.selected.fg)
+        } else {
+            (style.normal.bg, style.normal.fg)
+        };
+
+        let text_style = TextStyle {
+            font: assets.font.clone(),
+            font_size: style.size,
+            color: fg,
+        };
+
+        let (content, content_color) = if editing {
+            (
+                format!("{}{}", value.text, input_style.caret),
+                input_style.text,
+            )
+        } else if value.text.is_empty() {
+            (value.placeholder.clone(), input_style.placeholder)
+        } else {
+            (value.text.clone(), input_style.text)
+        };
+
+        let mut button = parent.spawn(ButtonBundle {
+            style: Style {
+                margin: style.margin,
+                padding: style.padding,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: BackgroundColor(bg),
+            ..default()
+        });
+        button.insert(ButtonComponent {
+            style: style.clone(),
+            selection: selection.clone(),
+            menu_identifier,
+            selected,
+            disabled: false,
+        });
+        if editing {
+            button.insert(EditingTextInput);
+        }
+        button.with_children(|parent| {
+            parent.spawn(text.bundle(&text_style));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        min_width: input_style.width,
+                        margin: input_style.margin,
+                        padding: input_style.padding,
+                        ..default()
+                    },
+                    background_color: BackgroundColor(input_style.background),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        content,
+                        TextStyle {
+                            color: content_color,
+                            ..text_style.clone()
+                        },
+                    ));
+                });
+        });
+    }
+}
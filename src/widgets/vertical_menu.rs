@@ -1,15 +1,27 @@
 use crate::{
+    input::BoundInput,
     style::{StyleEntry, Stylesheet},
     types::{
-        MenuAssets, MenuIcon, MenuItem, MenuSelection, NavigationEvent, Selections,
-        VerticalMenuComponent, WidgetId,
+        FocusTransition, ItemDescription, MenuAssets, MenuIcon, MenuItem, MenuSelection,
+        NavigationEvent, RenderedItem, RenderedMenu, ScrollOffsets, Selections, TooltipAnchor,
+        TooltipComponent, VerticalMenuComponent, WidgetId, WidgetLabel,
     },
     ScreenTrait,
 };
 use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
 
 use super::Widget;
-use super::{ButtonWidget, LabelWidget};
+use super::{
+    ButtonWidget, DescriptionWidget, KeyBindingWidget, LabelWidget, SliderWidget, TextInputWidget,
+};
+
+/// The entities of an already rendered vertical menu
+pub struct ExistingMenu<'r> {
+    pub entity: Entity,
+    pub rendered: &'r RenderedMenu,
+    pub rows: &'r [Entity],
+}
 
 pub struct VerticalMenu<'a, S>
 where
@@ -27,115 +39,487 @@ where
     pub style: Option<&'a Style>,
     // Overriding Bevy Background Color
     pub background: Option<&'a BackgroundColor>,
+    // Only show this many rows and scroll the rest
+    pub visible_rows: Option<usize>,
+    // The selectable index of the text input that is being edited
+    // or of the key binding that is capturing
+    pub editing: Option<usize>,
 }
 
 impl<'a, S> VerticalMenu<'a, S>
 where
     S: ScreenTrait + 'static,
 {
-    pub fn build(self, selections: &Selections, builder: &mut ChildBuilder) {
+    /// Build the menu or update an existing one. Only rows whose contents
+    /// changed are re-created. Returns the entity of the menu node.
+    pub fn build(
+        self,
+        selections: &Selections,
+        scrolls: &mut ScrollOffsets,
+        existing: Option<ExistingMenu>,
+        rendered_items: &Query<&RenderedItem<S>>,
+        commands: &mut Commands,
+    ) -> Entity {
         let VerticalMenu {
             id,
             items,
             stylesheet,
-            assets,
             ..
-        } = self;
-        if items.is_empty() {
-            return;
-        }
+        } = &self;
 
-        let style = self.style.cloned().unwrap_or_else(|| Style {
+        let mut style = self.style.cloned().unwrap_or_else(|| Style {
             align_items: AlignItems::FlexStart,
             flex_direction: FlexDirection::Column,
             padding: UiRect::all(Val::Px(stylesheet.vertical_spacing)),
             ..default()
         });
+        if items.is_empty() {
+            style.display = Display::None;
+        }
+        if self.visible_rows.is_some_and(|rows| rows < items.len()) {
+            style.overflow = Overflow::clip_y();
+        }
 
         let background_color = self
             .background
             .cloned()
             .unwrap_or_else(|| Color::NONE.into());
 
-        builder
-            .spawn(NodeBundle {
-                style,
-                background_color,
-                ..default()
+        let rendered_menu = RenderedMenu {
+            id: id.clone(),
+            style: style.clone(),
+            background: background_color,
+        };
+
+        // A different menu at the same position in the stack is rebuilt
+        let existing = match existing {
+            Some(existing) if &existing.rendered.id != id => {
+                commands.entity(existing.entity).despawn_recursive();
+                None
+            }
+            other => other,
+        };
+
+        let (entity, rows) = match existing {
+            Some(existing) => {
+                if existing.rendered.style != style
+                    || existing.rendered.background != background_color
+                {
+                    commands.entity(existing.entity).insert((
+                        style,
+                        background_color,
+                        rendered_menu,
+                    ));
+                }
+                (existing.entity, existing.rows)
+            }
+            None => {
+                let entity = commands
+                    .spawn(NodeBundle {
+                        style,
+                        background_color,
+                        ..default()
+                    })
+                    .insert((VerticalMenuComponent(id.clone()), rendered_menu))
+                    .id();
+                (entity, &[][..])
+            }
+        };
+
+        let (selected_idx, selectables) = Self::current_selection(id, items, selections);
+
+        // Menus without any selectable items (e.g. while a definition loads)
+        let selected = selectables
+            .get(selected_idx)
+            .map(|(_, item)| item.as_selection())
+            .unwrap_or(MenuSelection::None);
+
+        let bound_inputs: Vec<BoundInput> = items
+            .iter()
+            .filter_map(|item| match item.inner() {
+                MenuItem::KeyBinding(_, _, _, value) => value.input,
+                _ => None,
             })
-            .with_children(|parent| {
-                let (selected_idx, selectables) = Self::current_selection(&id, items, selections);
+            .collect();
+
+        let mut rendered_items_list = Vec::with_capacity(items.len());
+        let mut focused_position = None;
+        let mut index = 0;
+        for (position, item) in items.iter().enumerate() {
+            let item_selection = item.as_selection();
+            let focussed = (selected == item_selection) && item.is_selectable();
+            if focussed && focused_position.is_none() {
+                focused_position = Some(position);
+            }
+
+            rendered_items_list.push(RenderedItem {
+                item: item.clone(),
+                selectable_index: index,
+                focused: focussed,
+                editing: focussed && self.editing == Some(index),
+                conflict: match item.inner() {
+                    MenuItem::KeyBinding(_, _, _, value) => value.input.is_some_and(|input| {
+                        bound_inputs.iter().filter(|other| **other == input).count() > 1
+                    }),
+                    _ => false,
+                },
+            });
+
+            // Only increase for menu elements, so the indexes pair up
+            // with the `selectables` indexes
+            if item_selection != MenuSelection::None {
+                index += 1;
+            }
+        }
 
-                let selected = selectables[selected_idx].1.as_selection();
+        // All rows are rendered, the rows outside of the visible window are hidden
+        let mut stopped_scrolling = false;
+        let window = if let Some(visible) = self.visible_rows.filter(|rows| *rows < items.len()) {
+            let first_selectable = items.iter().position(|e| e.is_selectable());
+            let last_selectable = items.iter().rposition(|e| e.is_selectable());
+            let scroll = scrolls.0.entry(id.clone()).or_default();
+            scroll.update(
+                visible,
+                items.len(),
+                focused_position,
+                first_selectable,
+                last_selectable,
+            );
+            Some(scroll.offset..scroll.offset + visible)
+        } else {
+            stopped_scrolling = scrolls.0.remove(id).is_some();
+            None
+        };
 
-                let mut index = 0;
-                for item in items {
-                    let is_label = matches!(item, MenuItem::Label(_, _));
-                    let item_selection = item.as_selection();
-                    let focussed = (selected == item_selection) && !is_label;
+        // The scroll indicators are the only children that aren't rows
+        let (item_rows, mut indicators): (Vec<Entity>, Vec<Entity>) =
+            rows.iter().partition(|row| rendered_items.contains(**row));
 
-                    match item {
-                        MenuItem::Screen(t, i, _) => Self::add_item(
-                            assets,
-                            parent,
-                            i,
-                            &stylesheet.button,
-                            ButtonWidget::new(
-                                t,
-                                &stylesheet.button,
-                                (id.clone(), index),
-                                &item_selection,
-                                focussed,
-                            ),
-                        ),
-                        MenuItem::Action(t, i, _) => Self::add_item(
-                            assets,
-                            parent,
-                            i,
-                            &stylesheet.button,
-                            ButtonWidget::new(
-                                t,
-                                &stylesheet.button,
-                                (id.clone(), index),
-                                &item_selection,
-                                focussed,
-                            ),
-                        ),
-                        MenuItem::Label(t, i) => Self::add_item(
-                            assets,
-                            parent,
-                            i,
-                            &stylesheet.label,
-                            LabelWidget::new(t, &stylesheet.label),
-                        ),
-                        MenuItem::Headline(t, i) => Self::add_item(
-                            assets,
+        let mut children = Vec::with_capacity(rendered_items_list.len() + 2);
+        for (position, rendered) in rendered_items_list.into_iter().enumerate() {
+            let unchanged = item_rows
+                .get(position)
+                .copied()
+                .filter(|row| rendered_items.get(*row).ok() == Some(&rendered));
+
+            let row = match unchanged {
+                Some(row) => row,
+                None => {
+                    if let Some(old) = item_rows.get(position) {
+                        commands.entity(*old).despawn_recursive();
+                    }
+                    let mut row = Entity::PLACEHOLDER;
+                    commands.entity(entity).with_children(|parent| {
+                        row = self.spawn_item(
                             parent,
-                            i,
-                            &stylesheet.headline,
-                            LabelWidget::new(t, &stylesheet.headline),
-                        ),
-                        MenuItem::Image(i, s) => {
-                            let style = s.clone().unwrap_or_else(|| Style {
-                                align_self: AlignSelf::Center,
-                                ..Default::default()
-                            });
-                            parent.spawn(ImageBundle {
-                                style,
-                                image: i.clone().into(),
-                                ..Default::default()
-                            });
-                        }
-                    };
+                            &rendered.item,
+                            rendered.selectable_index,
+                            &rendered.item.as_selection(),
+                            rendered.focused,
+                            rendered.editing,
+                            rendered.conflict,
+                        );
+                    });
+                    self.add_tooltip(row, &rendered.item, commands);
+                    if let Some(transition) = self.focus_transition(
+                        item_rows
+                            .get(position)
+                            .and_then(|row| rendered_items.get(*row).ok()),
+                        &rendered,
+                    ) {
+                        commands.entity(row).insert(transition);
+                    }
+                    commands.entity(row).insert(rendered);
+                    row
+                }
+            };
+            children.push(row);
+        }
 
-                    // Only increase for menu elements, so the indexes pair up
-                    // with the `selectables` indexes
-                    if item_selection != MenuSelection::None {
-                        index += 1;
+        for stale in item_rows.iter().skip(children.len()) {
+            commands.entity(*stale).despawn_recursive();
+        }
+
+        // Scrolling only changes which rows are displayed, the rows stay
+        if let Some(window) = &window {
+            for (position, row) in children.iter().enumerate() {
+                Self::set_display(*row, window.contains(&position), commands);
+            }
+        } else if stopped_scrolling {
+            for row in children.iter() {
+                Self::set_display(*row, true, commands);
+            }
+        }
+
+        match (&window, &stylesheet.scroll_indicators) {
+            (Some(window), Some(texts)) => {
+                if indicators.len() != 2 {
+                    for indicator in indicators.drain(..) {
+                        commands.entity(indicator).despawn_recursive();
                     }
+                    commands.entity(entity).with_children(|parent| {
+                        for text in [&texts.up, &texts.down] {
+                            let indicator = Self::add_item(
+                                self.assets,
+                                parent,
+                                &MenuIcon::None,
+                                &stylesheet.label,
+                                LabelWidget::new(
+                                    &WidgetLabel::from(text.as_str()),
+                                    &stylesheet.label,
+                                ),
+                            );
+                            indicators.push(indicator);
+                        }
+                    });
                 }
-            })
-            .insert(VerticalMenuComponent(id));
+                // Hidden indicators keep their space, so the menu height doesn't change
+                let more_below = window.end < items.len();
+                Self::set_visible(indicators[0], window.start > 0, commands);
+                Self::set_visible(indicators[1], more_below, commands);
+                children.insert(0, indicators[0]);
+                children.push(indicators[1]);
+            }
+            _ => {
+                for indicator in indicators {
+                    commands.entity(indicator).despawn_recursive();
+                }
+            }
+        }
+
+        if children != rows {
+            commands.entity(entity).replace_children(&children);
+        }
+
+        entity
+    }
+
+    /// Show or hide a row without re-creating it
+    fn set_display(row: Entity, shown: bool, commands: &mut Commands) {
+        let display = if shown { Display::Flex } else { Display::None };
+        commands.entity(row).add(move |mut entity: EntityWorldMut| {
+            if let Some(mut style) = entity.get_mut::<Style>() {
+                if style.display != display {
+                    style.display = display;
+                }
+            }
+        });
+    }
+
+    fn set_visible(entity: Entity, visible: bool, commands: &mut Commands) {
+        let visibility = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        commands
+            .entity(entity)
+            .add(move |mut entity: EntityWorldMut| {
+                if let Some(mut current) = entity.get_mut::<Visibility>() {
+                    current.set_if_neq(visibility);
+                }
+            });
+    }
+
+    /// Without a description panel, the description of an item is shown in
+    /// a tooltip next to its row
+    fn add_tooltip(&self, row: Entity, item: &MenuItem<S>, commands: &mut Commands) {
+        let (None, Some(tooltip), Some(description)) = (
+            &self.stylesheet.description,
+            &self.stylesheet.tooltip,
+            item.description(),
+        ) else {
+            return;
+        };
+        commands
+            .entity(row)
+            .insert((TooltipAnchor, RelativeCursorPosition::default()))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            left: Val::Percent(100.0),
+                            top: Val::Px(0.0),
+                            max_width: tooltip.max_width,
+                            padding: tooltip.padding,
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: BackgroundColor(tooltip.background),
+                        visibility: Visibility::Hidden,
+                        // Above the rows that follow
+                        z_index: ZIndex::Global(i32::MAX),
+                        ..default()
+                    })
+                    .insert(TooltipComponent)
+                    .with_children(|parent| {
+                        DescriptionWidget::new(
+                            description,
+                            tooltip.text,
+                            tooltip.font_size,
+                            tooltip.image_size,
+                        )
+                        .build(parent, self.assets)
+                    });
+            });
+    }
+
+    /// Animate the button of a row whose item gained or lost the focus
+    fn focus_transition(
+        &self,
+        previous: Option<&RenderedItem<S>>,
+        current: &RenderedItem<S>,
+    ) -> Option<FocusTransition> {
+        let duration = self.stylesheet.transitions.focus_duration;
+        let previous = previous?;
+        let changed = previous.focused != current.focused
+            && previous.item.as_selection() == current.item.as_selection();
+        if duration <= 0.0 || !changed || current.item.is_disabled() {
+            return None;
+        }
+        let style = &self.stylesheet.button;
+        let (from, to) = if current.focused {
+            (style.normal.bg, style.selected.bg)
+        } else {
+            (style.selected.bg, style.normal.bg)
+        };
+        Some(FocusTransition {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn spawn_item(
+        &self,
+        parent: &mut ChildBuilder,
+        item: &MenuItem<S>,
+        index: usize,
+        item_selection: &MenuSelection<S>,
+        focussed: bool,
+        editing: bool,
+        conflict: bool,
+    ) -> Entity {
+        let VerticalMenu {
+            id,
+            stylesheet,
+            assets,
+            ..
+        } = self;
+        let disabled = item.is_disabled();
+        match item.inner() {
+            MenuItem::Screen(t, i, _) => Self::add_item(
+                assets,
+                parent,
+                i,
+                &stylesheet.button,
+                ButtonWidget::new(
+                    t,
+                    &stylesheet.button,
+                    (id.clone(), index),
+                    item_selection,
+                    focussed,
+                    disabled,
+                ),
+            ),
+            MenuItem::Action(t, i, _) => Self::add_item(
+                assets,
+                parent,
+                i,
+                &stylesheet.button,
+                ButtonWidget::new(
+                    t,
+                    &stylesheet.button,
+                    (id.clone(), index),
+                    item_selection,
+                    focussed,
+                    disabled,
+                ),
+            ),
+            MenuItem::Slider(t, i, a, v) => Self::add_item(
+                assets,
+                parent,
+                i,
+                &stylesheet.button,
+                SliderWidget::new(
+                    t,
+                    &stylesheet.button,
+                    &stylesheet.slider,
+                    (id.clone(), index),
+                    item_selection,
+                    *a,
+                    *v,
+                    focussed,
+                ),
+            ),
+            MenuItem::TextInput(t, i, _, v) => Self::add_item(
+                assets,
+                parent,
+                i,
+                &stylesheet.button,
+                TextInputWidget::new(
+                    t,
+                    &stylesheet.button,
+                    &stylesheet.text_input,
+                    (id.clone(), index),
+                    item_selection,
+                    v,
+                    focussed,
+                    editing,
+                ),
+            ),
+            MenuItem::KeyBinding(t, i, _, v) => Self::add_item(
+                assets,
+                parent,
+                i,
+                &stylesheet.button,
+                KeyBindingWidget::new(
+                    t,
+                    &stylesheet.button,
+                    &stylesheet.key_binding,
+                    (id.clone(), index),
+                    item_selection,
+                    v,
+                    focussed,
+                    editing,
+                    conflict,
+                ),
+            ),
+            MenuItem::Label(t, i) => Self::add_item(
+                assets,
+                parent,
+                i,
+                &stylesheet.label,
+                LabelWidget::new(t, &stylesheet.label),
+            ),
+            MenuItem::Headline(t, i) => Self::add_item(
+                assets,
+                parent,
+                i,
+                &stylesheet.headline,
+                LabelWidget::new(t, &stylesheet.headline),
+            ),
+            MenuItem::Image(i, s) => {
+                let style = s.clone().unwrap_or_else(|| Style {
+                    align_self: AlignSelf::Center,
+                    ..Default::default()
+                });
+                parent
+                    .spawn(ImageBundle {
+                        style,
+                        image: i.clone().into(),
+                        ..Default::default()
+                    })
+                    .id()
+            }
+            MenuItem::Described(..) | MenuItem::Confirmed(..) | MenuItem::Disabled(..) => {
+                unreachable!("`inner` removes the description, confirmation and disabled state")
+            }
+        }
     }
 
     pub fn apply_event(
@@ -145,17 +529,43 @@ where
         selections: &mut Selections,
     ) -> Option<MenuSelection<S>> {
         let (mut selectable_index, selectables) = Self::current_selection(&id, items, selections);
+        if selectables.is_empty() {
+            return None;
+        }
 
         let mut select_navigation = false;
 
         let mut selected = selectables[selectable_index].1.as_selection();
 
         match event {
-            NavigationEvent::Up if selectable_index > 0 => selectable_index -= 1,
-            NavigationEvent::Down if selectable_index < (selectables.len() - 1) => {
-                selectable_index += 1
+            // Disabled items are skipped
+            NavigationEvent::Up => {
+                if let Some(previous) = selectables[..selectable_index]
+                    .iter()
+                    .rposition(|(_, e)| !e.is_disabled())
+                {
+                    selectable_index = previous;
+                }
+            }
+            NavigationEvent::Down => {
+                if let Some(next) = selectables[selectable_index + 1..]
+                    .iter()
+                    .position(|(_, e)| !e.is_disabled())
+                {
+                    selectable_index += next + 1;
+                }
+            }
+            // Sliders are only changed via adjustments, text inputs start
+            // editing and key bindings start capturing
+            NavigationEvent::Select
+                if !selectables[selectable_index].1.is_disabled()
+                    && !matches!(
+                        selectables[selectable_index].1.inner(),
+                        MenuItem::Slider(..) | MenuItem::TextInput(..) | MenuItem::KeyBinding(..)
+                    ) =>
+            {
+                select_navigation = true
             }
-            NavigationEvent::Select => select_navigation = true,
             _ => (),
         }
 
@@ -163,7 +573,7 @@ where
             selected = selectables[selectable_index].1.as_selection();
         }
         for item in items {
-            let is_label = matches!(item, MenuItem::Label(_, _));
+            let is_label = matches!(item.inner(), MenuItem::Label(_, _));
             let item_selection = item.as_selection();
             let focussed = (selected == item_selection) && !is_label;
             if !is_label && (select_navigation && focussed) {
@@ -174,6 +584,42 @@ where
         None
     }
 
+    /// The number of selectable items
+    pub fn selectable_count(items: &[MenuItem<S>]) -> usize {
+        items.iter().filter(|e| e.is_selectable()).count()
+    }
+
+    /// The currently focused item and its selectable index, if there is one.
+    /// The item is returned without its description, confirmation and
+    /// disabled state, see `focused_entry`.
+    pub fn focused_item(
+        id: &WidgetId,
+        items: &'a [MenuItem<S>],
+        selections: &Selections,
+    ) -> Option<(usize, &'a MenuItem<S>)> {
+        Self::focused_entry(id, items, selections).map(|(index, item)| (index, item.inner()))
+    }
+
+    /// The currently focused item as it is in the menu, with its wrappers
+    pub fn focused_entry(
+        id: &WidgetId,
+        items: &'a [MenuItem<S>],
+        selections: &Selections,
+    ) -> Option<(usize, &'a MenuItem<S>)> {
+        let (selected_idx, selectables) = Self::current_selection(id, items, selections);
+        selectables.get(selected_idx).copied()
+    }
+
+    /// The description of the currently focused item, if it has one
+    pub fn focused_description(
+        id: &WidgetId,
+        items: &'a [MenuItem<S>],
+        selections: &Selections,
+    ) -> Option<&'a ItemDescription> {
+        let (selected_idx, selectables) = Self::current_selection(id, items, selections);
+        selectables.get(selected_idx)?.1.description()
+    }
+
     #[allow(clippy::type_complexity)]
     fn current_selection(
         id: &WidgetId,
@@ -189,12 +635,16 @@ where
         let selected_idx = selections.0.get(id).copied().unwrap_or_else(|| {
             let non_none = selectables
                 .iter()
-                .find(|(_, e)| e.as_selection() != MenuSelection::None)
+                .find(|(_, e)| e.as_selection() != MenuSelection::None && !e.is_disabled())
                 .map(|(i, _)| *i);
             non_none.unwrap_or_default()
         });
 
-        (selected_idx, selectables)
+        // Stale selections (e.g. after the menu shrunk) are clamped
+        (
+            selected_idx.min(selectables.len().saturating_sub(1)),
+            selectables,
+        )
     }
 
     fn add_item(
@@ -203,7 +653,7 @@ where
         icon: &MenuIcon,
         style: &StyleEntry,
         widget: impl Widget,
-    ) {
+    ) -> Entity {
         parent
             .spawn(NodeBundle {
                 style: Style {
@@ -228,6 +678,7 @@ where
                     });
                 }
                 widget.build(parent, assets);
-            });
+            })
+            .id()
     }
 }
@@ -0,0 +1,201 @@
+//! The menu that the integration tests are written against. It follows
+//! the basic example and adds the other item types.
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use bevy_quickmenu::definition::{MenuDefinition, MenuDefinitionAdapter};
+use bevy_quickmenu::input::BoundInput;
+use bevy_quickmenu::{
+    ActionTrait, Confirmation, Menu, MenuIcon, MenuItem, ScreenTrait, WidgetLabel,
+};
+
+#[derive(Debug, PartialEq, Eq, Event)]
+pub enum BasicEvent {
+    Close,
+    Quit,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BasicState {
+    pub boolean1: bool,
+    pub boolean2: bool,
+    pub volume: f32,
+    pub name: String,
+    pub jump: Option<BoundInput>,
+    pub crouch: Option<BoundInput>,
+    pub has_savegame: bool,
+    /// Only used by the screens that are resolved via `resolve_definition`
+    pub definition: MenuDefinition,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Actions {
+    Close,
+    Toggle1,
+    Toggle2,
+    Volume,
+    Name,
+    BindJump,
+    BindCrouch,
+    Continue,
+    Quit,
+}
+
+impl ActionTrait for Actions {
+    type State = BasicState;
+    type Event = BasicEvent;
+    fn handle(&self, state: &mut BasicState, event_writer: &mut EventWriter<BasicEvent>) {
+        match self {
+            Actions::Close => {
+                event_writer.send(BasicEvent::Close);
+            }
+            Actions::Quit => {
+                event_writer.send(BasicEvent::Quit);
+            }
+            Actions::Toggle1 => state.boolean1 = !state.boolean1,
+            Actions::Toggle2 => state.boolean2 = !state.boolean2,
+            _ => (),
+        }
+    }
+
+    fn set_value(&self, value: f32, state: &mut BasicState, _: &mut EventWriter<BasicEvent>) {
+        state.volume = value;
+    }
+
+    fn set_text(&self, text: &str, state: &mut BasicState, _: &mut EventWriter<BasicEvent>) {
+        state.name = text.to_string();
+    }
+
+    fn set_binding(
+        &self,
+        input: BoundInput,
+        state: &mut BasicState,
+        _: &mut EventWriter<BasicEvent>,
+    ) {
+        match self {
+            Actions::BindJump => state.jump = Some(input),
+            Actions::BindCrouch => state.crouch = Some(input),
+            _ => (),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Screens {
+    Root,
+    Booleans,
+    Settings,
+    Controls,
+    Empty,
+}
+
+impl ScreenTrait for Screens {
+    type Action = Actions;
+    type State = BasicState;
+    fn resolve(&self, state: &BasicState) -> Menu<Screens> {
+        match self {
+            Screens::Root => Menu::new(
+                "root",
+                vec![
+                    MenuItem::headline("Basic Example"),
+                    MenuItem::action("Close", Actions::Close).with_icon(MenuIcon::Back),
+                    MenuItem::label("A submenu"),
+                    MenuItem::screen("Boolean", Screens::Booleans),
+                    MenuItem::screen("Settings", Screens::Settings),
+                    MenuItem::screen("Controls", Screens::Controls),
+                    MenuItem::action("Quit", Actions::Quit)
+                        .with_confirmation(Confirmation::new("Quit to desktop?")),
+                ],
+            ),
+            Screens::Booleans => Menu::new(
+                "boolean",
+                vec![
+                    MenuItem::label("Toggles some booleans"),
+                    MenuItem::action("Toggle Boolean 1", Actions::Toggle1).checked(state.boolean1),
+                    MenuItem::action("Toggle Boolean 2", Actions::Toggle2).checked(state.boolean2),
+                ],
+            ),
+            Screens::Settings => Menu::new(
+                "settings",
+                vec![
+                    MenuItem::action("Continue", Actions::Continue).disabled(!state.has_savegame),
+                    MenuItem::slider("Volume", Actions::Volume, state.volume, 0.0..=1.0, 0.25),
+                    MenuItem::text_input("Name", Actions::Name, state.name.as_str())
+                        .with_max_length(4),
+                ],
+            ),
+            Screens::Controls => Menu::new(
+                "controls",
+                vec![
+                    MenuItem::label("Rebind the controls"),
+                    MenuItem::key_binding("Jump", Actions::BindJump, state.jump),
+                    MenuItem::key_binding("Crouch", Actions::BindCrouch, state.crouch),
+                ],
+            ),
+            Screens::Empty => Menu::new(
+                "empty",
+                vec![MenuItem::label(WidgetLabel::localized("nothing-here"))],
+            ),
+        }
+    }
+}
+
+impl MenuDefinitionAdapter for Screens {
+    fn definition(state: &BasicState) -> &MenuDefinition {
+        &state.definition
+    }
+
+    fn definition_mut(state: &mut BasicState) -> &mut MenuDefinition {
+        &mut state.definition
+    }
+
+    fn screen_id(&self) -> &str {
+        match self {
+            Screens::Root => "root",
+            Screens::Booleans => "boolean",
+            Screens::Settings => "settings",
+            Screens::Controls => "controls",
+            Screens::Empty => "empty",
+        }
+    }
+
+    fn screen_from_id(id: &str) -> Option<Self> {
+        match id {
+            "root" => Some(Screens::Root),
+            "boolean" => Some(Screens::Booleans),
+            "settings" => Some(Screens::Settings),
+            "controls" => Some(Screens::Controls),
+            "empty" => Some(Screens::Empty),
+            _ => None,
+        }
+    }
+
+    fn action_from_id(id: &str) -> Option<Actions> {
+        match id {
+            "close" => Some(Actions::Close),
+            "continue" => Some(Actions::Continue),
+            "quit" => Some(Actions::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// A second screen type, for apps that add several `QuickMenuPlugin`s
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Inventory {
+    Items,
+}
+
+impl ScreenTrait for Inventory {
+    type Action = Actions;
+    type State = BasicState;
+    fn resolve(&self, state: &BasicState) -> Menu<Inventory> {
+        Menu::new(
+            "root",
+            vec![
+                MenuItem::action("Toggle Boolean 1", Actions::Toggle1).checked(state.boolean1),
+                MenuItem::action("Toggle Boolean 2", Actions::Toggle2).checked(state.boolean2),
+            ],
+        )
+    }
+}
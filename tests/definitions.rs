@@ -0,0 +1,92 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_quickmenu::definition::{
+    DefinitionProblem, MenuDefinition, MenuDefinitionAdapter, MenuDefinitionHandle,
+    MenuDefinitionPlugin,
+};
+use bevy_quickmenu::testing::MenuTester;
+use bevy_quickmenu::{MenuItem, QuickMenu};
+use common::{Actions, BasicState, Screens};
+
+const DEFINITION: &str = r#"(
+    screens: {
+        "root": (
+            items: [
+                Screen(label: "Settings", screen: "settings"),
+                Screen(label: "Extras", screen: "extras"),
+                Action(label: "Quit", action: "quit", confirmation: Some("Sure?")),
+                Action(label: "Jump", action: "jump"),
+            ],
+        ),
+    },
+)"#;
+
+fn state() -> BasicState {
+    BasicState {
+        definition: ron::de::from_str(DEFINITION).unwrap(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn unknown_ids_are_skipped() {
+    let state = state();
+    let menu = Screens::Root.resolve_definition(&state);
+    assert_eq!(menu.entries.len(), 2);
+    assert!(matches!(
+        menu.entries[0],
+        MenuItem::Screen(_, _, Screens::Settings)
+    ));
+    assert!(matches!(
+        menu.entries[1],
+        MenuItem::Confirmed(ref item, _) if matches!(**item, MenuItem::Action(_, _, Actions::Quit))
+    ));
+    assert!(Screens::Settings
+        .resolve_definition(&state)
+        .entries
+        .is_empty());
+}
+
+#[test]
+fn problems_are_reported_once() {
+    let definition: MenuDefinition = ron::de::from_str(DEFINITION).unwrap();
+    let problems = Screens::definition_problems(&definition, &[Screens::Root, Screens::Root]);
+    assert_eq!(
+        problems,
+        vec![
+            DefinitionProblem::MissingScreen("settings".to_string()),
+            DefinitionProblem::UnknownScreen("extras".to_string()),
+            DefinitionProblem::UnknownAction("jump".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn definitions_reach_every_menu() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    let app = tester.app();
+    app.add_plugins(MenuDefinitionPlugin::<Screens>::new());
+    let definition: MenuDefinition = ron::de::from_str(DEFINITION).unwrap();
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<MenuDefinition>>()
+        .add(definition);
+    app.insert_resource(MenuDefinitionHandle(handle));
+    let instance = app
+        .world_mut()
+        .spawn((
+            NodeBundle::default(),
+            QuickMenu::new(BasicState::default(), Screens::Root, None),
+        ))
+        .id();
+    tester.update();
+
+    assert_eq!(tester.state().definition.screens.len(), 1);
+    let instance = tester
+        .app()
+        .world()
+        .get::<QuickMenu<Screens>>(instance)
+        .unwrap();
+    assert_eq!(instance.menu_state().state().definition.screens.len(), 1);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_quickmenu::input::BoundInput;
+use bevy_quickmenu::testing::MenuTester;
+use bevy_quickmenu::{BindingConflict, NavigationEvent};
+use common::{Actions, BasicState, Screens};
+
+#[derive(Resource, Default)]
+struct Conflicts(Vec<BindingConflict<Screens>>);
+
+fn read_conflicts(
+    mut reader: EventReader<BindingConflict<Screens>>,
+    mut conflicts: ResMut<Conflicts>,
+) {
+    conflicts.0.extend(reader.read().cloned());
+}
+
+#[test]
+fn conflicts_are_found_behind_non_selectable_items() {
+    // The controls screen starts with a label, so the positions of the
+    // bindings differ from their selectable indices
+    let state = BasicState {
+        jump: Some(BoundInput::Key(KeyCode::KeyW)),
+        ..default()
+    };
+    let mut tester = MenuTester::new(state, Screens::Root);
+    tester
+        .app()
+        .init_resource::<Conflicts>()
+        .add_systems(Update, read_conflicts);
+    tester.send_all([NavigationEvent::Down; 3]);
+    tester.send(NavigationEvent::Select);
+    assert_eq!(tester.stack(), &[Screens::Root, Screens::Controls]);
+
+    // Bind crouch to the key of jump
+    tester.send_all([NavigationEvent::Down, NavigationEvent::Select]);
+    tester.press_key(KeyCode::KeyW);
+    assert_eq!(tester.state().crouch, Some(BoundInput::Key(KeyCode::KeyW)));
+    assert_eq!(
+        tester.app().world().resource::<Conflicts>().0,
+        vec![BindingConflict {
+            action: Actions::BindCrouch,
+            input: BoundInput::Key(KeyCode::KeyW),
+            conflicts: vec![Actions::BindJump],
+        }]
+    );
+
+    // Binding the same key again doesn't conflict with itself
+    tester.send(NavigationEvent::Select);
+    tester.press_key(KeyCode::KeyW);
+    assert_eq!(tester.app().world().resource::<Conflicts>().0.len(), 2);
+    assert_eq!(
+        tester.app().world().resource::<Conflicts>().0[1].conflicts,
+        vec![Actions::BindJump]
+    );
+}
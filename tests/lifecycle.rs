@@ -0,0 +1,134 @@
+mod common;
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy_quickmenu::testing::MenuTester;
+use bevy_quickmenu::{
+    FocusChanged, MenuClosed, MenuSelection, MenuState, NavigationEvent, QuickMenu, ScreenPopped,
+    ScreenPushed,
+};
+use common::{Actions, BasicState, Screens};
+
+fn cleanup(tester: &mut MenuTester<Screens>) -> usize {
+    tester
+        .app()
+        .world_mut()
+        .run_system_once(|mut commands: Commands| bevy_quickmenu::cleanup(&mut commands));
+    tester.update();
+    let world = tester.app().world_mut();
+    let closed = world.resource::<Events<MenuClosed>>().len();
+    world.resource_mut::<Events<MenuClosed>>().clear();
+    closed
+}
+
+#[test]
+fn cleanup_only_reports_open_menus_as_closed() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    assert_eq!(cleanup(&mut tester), 1);
+    assert!(!tester
+        .app()
+        .world()
+        .contains_resource::<MenuState<Screens>>());
+
+    // There is no menu left to close
+    assert_eq!(cleanup(&mut tester), 0);
+}
+
+#[test]
+fn sliders_are_focused_as_sliders() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    tester.send_all([
+        NavigationEvent::Down,
+        NavigationEvent::Down,
+        NavigationEvent::Select,
+    ]);
+    let focus = tester
+        .app()
+        .world_mut()
+        .resource_mut::<Events<FocusChanged<Screens>>>()
+        .drain()
+        .last()
+        .expect("the focus moved onto the slider");
+    assert_eq!(focus.new, MenuSelection::Slider(Actions::Volume));
+    assert_ne!(focus.new, MenuSelection::Action(Actions::Volume));
+}
+
+fn drain<E: Event>(tester: &mut MenuTester<Screens>) -> Vec<E> {
+    tester
+        .app()
+        .world_mut()
+        .resource_mut::<Events<E>>()
+        .drain()
+        .collect()
+}
+
+#[test]
+fn programmatic_navigation_is_reported() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    let mut menu_state = tester
+        .app()
+        .world_mut()
+        .resource_mut::<MenuState<Screens>>();
+    menu_state.push_screen(Screens::Settings);
+    menu_state.push_screen(Screens::Controls);
+    tester.update();
+    assert_eq!(
+        drain::<ScreenPushed<Screens>>(&mut tester),
+        vec![
+            ScreenPushed(Screens::Settings),
+            ScreenPushed(Screens::Controls)
+        ]
+    );
+
+    tester
+        .app()
+        .world_mut()
+        .resource_mut::<MenuState<Screens>>()
+        .replace_stack(vec![Screens::Root, Screens::Booleans]);
+    tester.update();
+    assert_eq!(
+        drain::<ScreenPopped<Screens>>(&mut tester),
+        vec![
+            ScreenPopped(Screens::Controls),
+            ScreenPopped(Screens::Settings)
+        ]
+    );
+    assert_eq!(
+        drain::<ScreenPushed<Screens>>(&mut tester),
+        vec![ScreenPushed(Screens::Booleans)]
+    );
+
+    // Input is reported once
+    tester.send(NavigationEvent::Back);
+    assert_eq!(
+        drain::<ScreenPopped<Screens>>(&mut tester),
+        vec![ScreenPopped(Screens::Booleans)]
+    );
+    assert!(drain::<ScreenPushed<Screens>>(&mut tester).is_empty());
+}
+
+#[test]
+fn only_rendered_instances_are_reported_as_closed() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    drain::<MenuClosed>(&mut tester);
+    let world = tester.app().world_mut();
+    let rendered = world
+        .spawn((
+            NodeBundle::default(),
+            QuickMenu::new(BasicState::default(), Screens::Root, None),
+        ))
+        .id();
+    tester.update();
+    let world = tester.app().world_mut();
+    world.entity_mut(rendered).remove::<QuickMenu<Screens>>();
+    // Removed before it was rendered
+    let unrendered = world
+        .spawn((
+            NodeBundle::default(),
+            QuickMenu::new(BasicState::default(), Screens::Root, None),
+        ))
+        .id();
+    world.entity_mut(unrendered).remove::<QuickMenu<Screens>>();
+    tester.update();
+    assert_eq!(drain::<MenuClosed>(&mut tester).len(), 1);
+}
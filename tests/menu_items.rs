@@ -0,0 +1,96 @@
+mod common;
+
+use bevy_quickmenu::{Confirmation, ConfirmationAnswer, MenuIcon, MenuItem, WidgetLabel};
+use common::{Actions, Screens};
+
+/// The names of the wrappers around an item, from the outside in, and the
+/// wrapped item
+fn layers(item: &MenuItem<Screens>) -> (Vec<&'static str>, &MenuItem<Screens>) {
+    let (name, inner) = match item {
+        MenuItem::Described(inner, _) => ("Described", inner),
+        MenuItem::Confirmed(inner, _) => ("Confirmed", inner),
+        MenuItem::Disabled(inner) => ("Disabled", inner),
+        item => return (Vec::new(), item),
+    };
+    let (mut names, item) = layers(inner);
+    names.insert(0, name);
+    (names, item)
+}
+
+#[test]
+fn disabled_wraps_screens_and_actions() {
+    let item = MenuItem::<Screens>::action("Continue", Actions::Continue).disabled(true);
+    assert!(matches!(
+        item,
+        MenuItem::Disabled(ref inner) if matches!(**inner, MenuItem::Action(_, _, Actions::Continue))
+    ));
+    assert!(matches!(
+        item.disabled(false),
+        MenuItem::Action(_, _, Actions::Continue)
+    ));
+
+    // Disabling twice wraps the item once
+    let item = MenuItem::<Screens>::screen("Settings", Screens::Settings)
+        .disabled(true)
+        .disabled(true);
+    assert!(matches!(
+        item,
+        MenuItem::Disabled(ref inner) if matches!(**inner, MenuItem::Screen(_, _, Screens::Settings))
+    ));
+
+    let item = MenuItem::<Screens>::label("Label").disabled(true);
+    assert!(matches!(item, MenuItem::Label(..)));
+}
+
+#[test]
+fn disabled_items_keep_their_icon() {
+    let item = MenuItem::<Screens>::action("Continue", Actions::Continue)
+        .disabled(true)
+        .checked(true);
+    assert!(matches!(
+        item,
+        MenuItem::Disabled(ref inner)
+            if matches!(**inner, MenuItem::Action(_, MenuIcon::Checked, Actions::Continue))
+    ));
+}
+
+#[test]
+fn disabled_keeps_the_other_wrappers() {
+    let item = MenuItem::<Screens>::action("Continue", Actions::Continue)
+        .disabled(true)
+        .disabled(true)
+        .with_description("Loads the last save")
+        .with_confirmation(Confirmation::new("Sure?"))
+        .checked(true);
+    let (names, inner) = layers(&item);
+    assert_eq!(names, vec!["Confirmed", "Described", "Disabled"]);
+    assert!(matches!(
+        inner,
+        MenuItem::Action(_, MenuIcon::Checked, Actions::Continue)
+    ));
+
+    let item = item.disabled(false);
+    assert_eq!(layers(&item).0, vec!["Confirmed", "Described"]);
+}
+
+#[test]
+fn confirmation_only_wraps_actions() {
+    let confirmation = Confirmation::new("Quit?").with_yes("Quit");
+    assert_eq!(confirmation.focus, ConfirmationAnswer::No);
+    assert_eq!(confirmation.no, WidgetLabel::from("No"));
+
+    let screen = MenuItem::<Screens>::screen("Settings", Screens::Settings)
+        .with_confirmation(confirmation.clone());
+    assert!(matches!(screen, MenuItem::Screen(..)));
+
+    // Confirming again replaces the confirmation
+    let action = MenuItem::<Screens>::action("Quit", Actions::Quit)
+        .with_confirmation(confirmation)
+        .with_confirmation(Confirmation::new("Really?").with_focus(ConfirmationAnswer::Yes));
+    let MenuItem::Confirmed(inner, confirmation) = action else {
+        panic!("the action is not confirmed");
+    };
+    assert!(matches!(*inner, MenuItem::Action(_, _, Actions::Quit)));
+    assert_eq!(confirmation.text, WidgetLabel::from("Really?"));
+    assert_eq!(confirmation.focus, ConfirmationAnswer::Yes);
+}
@@ -0,0 +1,272 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_quickmenu::input::InputDevice;
+use bevy_quickmenu::testing::MenuTester;
+use bevy_quickmenu::{DeviceNavigationEvent, MenuItem, MenuState, NavigationEvent};
+use common::{Actions, BasicEvent, BasicState, Screens};
+
+fn tester() -> MenuTester<Screens> {
+    MenuTester::new(BasicState::default(), Screens::Root)
+}
+
+/// Focus the item of the root screen at a selectable index and select it
+fn open(tester: &mut MenuTester<Screens>, index: usize) {
+    for _ in 0..index {
+        tester.send(NavigationEvent::Down);
+    }
+    tester.send(NavigationEvent::Select);
+}
+
+#[test]
+fn renders_the_root_screen() {
+    let tester = tester();
+    assert_eq!(tester.stack(), &[Screens::Root]);
+    assert_eq!(
+        tester.labels(),
+        vec![vec![
+            "Basic Example",
+            "Close",
+            "A submenu",
+            "Boolean",
+            "Settings",
+            "Controls",
+            "Quit"
+        ]]
+    );
+    assert!(matches!(
+        tester.focused_item(),
+        Some(MenuItem::Action(_, _, Actions::Close))
+    ));
+}
+
+#[test]
+fn pushes_and_pops_screens() {
+    let mut tester = tester();
+    open(&mut tester, 1);
+    assert_eq!(tester.stack(), &[Screens::Root, Screens::Booleans]);
+    assert_eq!(tester.labels()[1][0], "Toggles some booleans");
+
+    tester.send(NavigationEvent::Select);
+    assert!(tester.state().boolean1);
+    tester
+        .send(NavigationEvent::Down)
+        .send(NavigationEvent::Select);
+    assert!(tester.state().boolean2);
+
+    tester.send(NavigationEvent::Back);
+    assert_eq!(tester.stack(), &[Screens::Root]);
+    assert_eq!(tester.labels().len(), 1);
+}
+
+#[test]
+fn actions_emit_their_events() {
+    let mut tester = tester();
+    tester.send(NavigationEvent::Select);
+    assert_eq!(tester.take_events(), vec![BasicEvent::Close]);
+    assert!(tester.events().is_empty());
+}
+
+#[test]
+fn disabled_items_are_skipped() {
+    let mut tester = tester();
+    open(&mut tester, 2);
+    assert!(matches!(
+        tester.focused_item(),
+        Some(MenuItem::Slider(_, _, Actions::Volume, _))
+    ));
+    tester.send(NavigationEvent::Up);
+    assert!(matches!(
+        tester.focused_item(),
+        Some(MenuItem::Slider(_, _, Actions::Volume, _))
+    ));
+
+    tester
+        .app()
+        .world_mut()
+        .resource_mut::<MenuState<Screens>>()
+        .state_mut()
+        .has_savegame = true;
+    tester.update().send(NavigationEvent::Up);
+    assert!(matches!(
+        tester.focused_item(),
+        Some(MenuItem::Action(_, _, Actions::Continue))
+    ));
+}
+
+#[test]
+fn sliders_snap_to_their_steps() {
+    let mut tester = tester();
+    open(&mut tester, 2);
+    tester.send_all([NavigationEvent::Right, NavigationEvent::Right]);
+    assert_eq!(tester.state().volume, 0.5);
+    tester.send_all([NavigationEvent::Right; 5]);
+    assert_eq!(tester.state().volume, 1.0);
+    tester.send(NavigationEvent::Left);
+    assert_eq!(tester.state().volume, 0.75);
+    assert_eq!(tester.labels()[1][1], "Volume 0.75");
+}
+
+#[test]
+fn text_inputs_respect_the_max_length() {
+    let mut tester = tester();
+    open(&mut tester, 2);
+    tester
+        .send(NavigationEvent::Down)
+        .send(NavigationEvent::Select)
+        .type_text("Alice");
+    assert_eq!(tester.state().name, "Alic");
+    tester.press_key(KeyCode::Backspace);
+    assert_eq!(tester.state().name, "Ali");
+
+    // Enter ends editing, the keys navigate again
+    tester.press_key(KeyCode::Enter).send(NavigationEvent::Back);
+    assert_eq!(tester.stack(), &[Screens::Root]);
+    assert_eq!(tester.state().name, "Ali");
+}
+
+#[test]
+fn escape_restores_the_text_from_before_editing() {
+    let mut tester = MenuTester::new(
+        BasicState {
+            name: "Bob".to_string(),
+            ..Default::default()
+        },
+        Screens::Root,
+    );
+    open(&mut tester, 2);
+    tester
+        .send(NavigationEvent::Down)
+        .send(NavigationEvent::Select)
+        .press_key(KeyCode::Backspace)
+        .type_text("x");
+    assert_eq!(tester.state().name, "Box");
+
+    // Escape ends editing as well, the keys navigate again
+    tester.press_key(KeyCode::Escape);
+    assert_eq!(tester.state().name, "Bob");
+    tester.send(NavigationEvent::Back);
+    assert_eq!(tester.stack(), &[Screens::Root]);
+}
+
+#[test]
+fn confirmation_dialog_traps_the_focus() {
+    let mut tester = tester();
+    open(&mut tester, 4);
+    assert_eq!(tester.menu_state().confirming(), Some(Actions::Quit));
+    assert!(tester.events().is_empty());
+
+    // No is focused by default, the stack doesn't change
+    tester.send_all([NavigationEvent::Down, NavigationEvent::Down]);
+    tester.send(NavigationEvent::Select);
+    assert_eq!(tester.menu_state().confirming(), None);
+    assert!(tester.events().is_empty());
+
+    tester.send(NavigationEvent::Select);
+    tester.send(NavigationEvent::Back);
+    assert_eq!(tester.menu_state().confirming(), None);
+    assert_eq!(tester.stack(), &[Screens::Root]);
+    assert!(tester.events().is_empty());
+
+    tester
+        .send(NavigationEvent::Select)
+        .send(NavigationEvent::Left)
+        .send(NavigationEvent::Select);
+    assert_eq!(tester.menu_state().confirming(), None);
+    assert_eq!(tester.take_events(), vec![BasicEvent::Quit]);
+}
+
+#[derive(Resource, Default)]
+struct ReadEvents(Vec<DeviceNavigationEvent>);
+
+fn read_events(mut reader: EventReader<DeviceNavigationEvent>, mut events: ResMut<ReadEvents>) {
+    events.0.extend(reader.read().copied());
+}
+
+#[test]
+fn keyboard_input_is_tagged_with_its_device() {
+    let mut tester = tester();
+    tester
+        .app()
+        .init_resource::<ReadEvents>()
+        .add_systems(Update, read_events);
+    tester.press_key(KeyCode::ArrowDown);
+    assert!(matches!(
+        tester.focused_item(),
+        Some(MenuItem::Screen(_, _, Screens::Booleans))
+    ));
+    assert_eq!(
+        tester.app().world().resource::<ReadEvents>().0,
+        vec![DeviceNavigationEvent {
+            event: NavigationEvent::Down,
+            device: InputDevice::Keyboard,
+        }]
+    );
+}
+
+#[derive(Resource, Default)]
+struct ReadPlainEvents(Vec<NavigationEvent>);
+
+fn read_plain_events(
+    mut reader: EventReader<NavigationEvent>,
+    mut events: ResMut<ReadPlainEvents>,
+) {
+    events.0.extend(reader.read().copied());
+}
+
+#[test]
+fn keyboard_input_is_mirrored_but_applied_once() {
+    let mut tester = tester();
+    tester
+        .app()
+        .init_resource::<ReadPlainEvents>()
+        .add_systems(Update, read_plain_events);
+    tester.press_key(KeyCode::ArrowDown);
+    assert_eq!(
+        tester.app().world().resource::<ReadPlainEvents>().0,
+        vec![NavigationEvent::Down]
+    );
+    // Moved by one row, not once for each event
+    assert!(matches!(
+        tester.focused_item(),
+        Some(MenuItem::Screen(_, _, Screens::Booleans))
+    ));
+}
+
+#[test]
+fn held_keys_do_not_repeat_into_the_next_menu() {
+    let mut tester = tester();
+    tester
+        .app()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .world_mut()
+        .send_event(KeyboardInput {
+            key_code: KeyCode::ArrowDown,
+            logical_key: Key::ArrowDown,
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+    tester.update();
+    tester
+        .app()
+        .world_mut()
+        .remove_resource::<MenuState<Screens>>();
+    tester.advance(2);
+
+    // The key is still held, long enough to repeat several times
+    tester
+        .app()
+        .insert_resource(MenuState::new(BasicState::default(), Screens::Root, None));
+    tester.advance(20);
+    assert!(matches!(
+        tester.focused_item(),
+        Some(MenuItem::Screen(_, _, Screens::Booleans))
+    ));
+}
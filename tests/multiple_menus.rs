@@ -0,0 +1,82 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_quickmenu::testing::MenuTester;
+use bevy_quickmenu::{
+    MenuFocus, MenuId, MenuItem, MenuOptions, MenuResources, MenuState, NavigationEvent,
+    PrimaryMenu, QuickMenuPlugin,
+};
+use common::{Actions, BasicState, Inventory, Screens};
+
+/// A tester for the `Screens` menu with a second plugin and menu for the
+/// `Inventory` screens, which is opened last
+fn tester() -> MenuTester<Screens> {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    let options = MenuOptions {
+        font: Some("fonts/inventory.ttf"),
+        ..Default::default()
+    };
+    tester
+        .app()
+        .add_plugins(QuickMenuPlugin::<Inventory>::with_options(options))
+        .insert_resource(MenuState::new(
+            BasicState::default(),
+            Inventory::Items,
+            None,
+        ));
+    tester.advance(2);
+    tester
+}
+
+/// The selected row of the `Inventory` menu
+fn inventory_selection(tester: &mut MenuTester<Screens>) -> Option<usize> {
+    let resources = tester.app().world().resource::<MenuResources<Inventory>>();
+    resources.selections.0.values().next().copied()
+}
+
+#[test]
+fn plugins_of_different_screen_types_share_the_input_systems() {
+    let mut tester = tester();
+    let world = tester.app().world_mut();
+    let menus = world
+        .query_filtered::<(), With<PrimaryMenu>>()
+        .iter(world)
+        .count();
+    assert_eq!(menus, 2);
+}
+
+#[test]
+fn plugins_keep_their_own_assets() {
+    let mut tester = tester();
+    let world = tester.app().world();
+    assert_ne!(
+        world.resource::<MenuResources<Screens>>().assets.font,
+        world.resource::<MenuResources<Inventory>>().assets.font
+    );
+}
+
+#[test]
+fn input_only_moves_the_focused_menu() {
+    let mut tester = tester();
+    let before = inventory_selection(&mut tester);
+    tester.send(NavigationEvent::Down);
+    assert_ne!(inventory_selection(&mut tester), before);
+    assert!(matches!(
+        tester.focused_item(),
+        Some(MenuItem::Action(_, _, Actions::Close))
+    ));
+
+    // Both menus use the same screen id, but not the same selections
+    let moved = inventory_selection(&mut tester);
+    tester
+        .app()
+        .world_mut()
+        .resource_mut::<MenuFocus>()
+        .focus(MenuId::resource::<Screens>());
+    tester.send(NavigationEvent::Down);
+    assert!(matches!(
+        tester.focused_item(),
+        Some(MenuItem::Screen(_, _, Screens::Booleans))
+    ));
+    assert_eq!(inventory_selection(&mut tester), moved);
+}
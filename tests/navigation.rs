@@ -0,0 +1,70 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_quickmenu::testing::MenuTester;
+use bevy_quickmenu::MenuState;
+use common::{BasicState, Screens};
+
+fn menu_state(tester: &mut MenuTester<Screens>) -> Mut<'_, MenuState<Screens>> {
+    tester
+        .app()
+        .world_mut()
+        .resource_mut::<MenuState<Screens>>()
+}
+
+#[test]
+fn screens_can_be_pushed_and_popped() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    assert_eq!(tester.menu_state().current_screen(), Some(Screens::Root));
+
+    menu_state(&mut tester).push_screen(Screens::Settings);
+    menu_state(&mut tester).push_screen(Screens::Controls);
+    tester.update();
+    assert_eq!(
+        tester.stack(),
+        &[Screens::Root, Screens::Settings, Screens::Controls]
+    );
+    assert_eq!(
+        tester.menu_state().current_screen(),
+        Some(Screens::Controls)
+    );
+    assert_eq!(tester.labels()[2][0], "Rebind the controls");
+
+    assert_eq!(
+        menu_state(&mut tester).pop_screen(),
+        Some(Screens::Controls)
+    );
+    tester.update();
+    assert_eq!(tester.stack(), &[Screens::Root, Screens::Settings]);
+    assert_eq!(tester.labels().len(), 2);
+}
+
+#[test]
+fn the_root_screen_stays() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    assert_eq!(menu_state(&mut tester).pop_screen(), None);
+
+    menu_state(&mut tester).replace_stack(vec![Screens::Root, Screens::Settings]);
+    menu_state(&mut tester).pop_to_root();
+    assert_eq!(tester.stack(), &[Screens::Root]);
+
+    // An empty stack is ignored
+    menu_state(&mut tester).replace_stack(Vec::new());
+    assert_eq!(tester.menu_state().current_screen(), Some(Screens::Root));
+}
+
+#[test]
+fn the_stack_can_be_replaced() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    menu_state(&mut tester).replace_stack(vec![Screens::Settings, Screens::Booleans]);
+    tester.update();
+    assert_eq!(tester.stack(), &[Screens::Settings, Screens::Booleans]);
+    assert_eq!(
+        tester.menu_state().current_screen(),
+        Some(Screens::Booleans)
+    );
+
+    // The first screen is the new root
+    menu_state(&mut tester).pop_to_root();
+    assert_eq!(tester.stack(), &[Screens::Settings]);
+}
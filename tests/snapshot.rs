@@ -0,0 +1,103 @@
+mod common;
+
+use bevy_quickmenu::localization::{LocalizationArg, MenuLocalization};
+use bevy_quickmenu::snapshot::MenuSnapshot;
+use bevy_quickmenu::testing::MenuTester;
+use bevy_quickmenu::NavigationEvent;
+use common::{BasicState, Screens};
+
+#[test]
+fn outline_shows_the_stack_and_the_focus() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    tester.send_all([
+        NavigationEvent::Down,
+        NavigationEvent::Select,
+        NavigationEvent::Select,
+    ]);
+    assert_eq!(
+        tester.snapshot().to_string(),
+        r#"Screen Root "root"
+    Headline "Basic Example"
+    Action "Close" [icon: Back]
+    Label "A submenu"
+  > Screen "Boolean"
+    Screen "Settings"
+    Screen "Controls"
+    Action "Quit" [confirmation: "Quit to desktop?"]
+Screen Booleans "boolean"
+    Label "Toggles some booleans"
+  > Action "Toggle Boolean 1" [checked]
+    Action "Toggle Boolean 2" [unchecked]
+"#
+    );
+}
+
+#[test]
+fn outline_shows_values_and_disabled_items() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    tester.send_all([
+        NavigationEvent::Down,
+        NavigationEvent::Down,
+        NavigationEvent::Select,
+        NavigationEvent::Right,
+    ]);
+    let snapshot = tester.snapshot();
+    assert_eq!(
+        snapshot.to_string().lines().skip(8).collect::<Vec<_>>(),
+        vec![
+            r#"Screen Settings "settings""#,
+            r#"    Action "Continue" [disabled]"#,
+            r#"  > Slider "Volume" = "0.25""#,
+            r#"    TextInput "Name" = """#,
+        ]
+    );
+}
+
+#[test]
+fn outline_shows_the_dialog() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    tester.send_all([NavigationEvent::Down; 4]);
+    tester.send(NavigationEvent::Select);
+    let outline = tester.snapshot().to_string();
+    assert!(
+        outline.ends_with(
+            r#"Dialog "Quit to desktop?"
+    Yes "Yes"
+  > No "No"
+"#
+        ),
+        "{outline}"
+    );
+}
+
+#[test]
+fn snapshot_round_trips_through_json() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    tester.send_all([NavigationEvent::Down, NavigationEvent::Select]);
+    let snapshot = tester.snapshot();
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let restored: MenuSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, snapshot);
+    assert_eq!(restored.screens.len(), 2);
+}
+
+fn localize(_language: &str, key: &str, _args: &[(String, LocalizationArg)]) -> Option<String> {
+    (key == "nothing-here").then(|| "Nothing here".to_string())
+}
+
+#[test]
+fn outline_shows_localized_labels() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Empty);
+    assert_eq!(
+        tester.snapshot().to_string(),
+        "Screen Empty \"empty\"\n    Label \"nothing-here\"\n"
+    );
+
+    tester
+        .app()
+        .insert_resource(MenuLocalization::new("en", localize));
+    assert_eq!(
+        tester.snapshot().to_string(),
+        "Screen Empty \"empty\"\n    Label \"Nothing here\"\n"
+    );
+}
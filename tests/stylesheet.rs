@@ -0,0 +1,120 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_quickmenu::style::{Stylesheet, StylesheetHandle};
+use bevy_quickmenu::testing::MenuTester;
+use bevy_quickmenu::{MenuState, QuickMenu, QuickMenuPlugin};
+use common::{BasicEvent, BasicState, Screens};
+
+fn sheet(vertical_spacing: f32) -> Stylesheet {
+    Stylesheet {
+        vertical_spacing,
+        ..Default::default()
+    }
+}
+
+fn spacing(app: &App) -> f32 {
+    app.world()
+        .resource::<MenuState<Screens>>()
+        .stylesheet()
+        .vertical_spacing
+}
+
+#[test]
+fn handle_is_applied_when_loaded_and_changed() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        bevy::input::InputPlugin,
+        HierarchyPlugin,
+    ))
+    .init_asset::<Font>()
+    .init_asset::<Image>()
+    .add_event::<BasicEvent>()
+    .add_plugins(QuickMenuPlugin::<Screens>::new())
+    .insert_resource(MenuState::new(BasicState::default(), Screens::Root, None));
+    app.update();
+    assert_eq!(spacing(&app), Stylesheet::default().vertical_spacing);
+
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<Stylesheet>>()
+        .add(sheet(30.0));
+    app.insert_resource(StylesheetHandle(handle.clone()));
+    app.update();
+    app.update();
+    assert_eq!(spacing(&app), 30.0);
+
+    // Changes of the asset, e.g. on disk, are applied as well
+    app.world_mut()
+        .resource_mut::<Assets<Stylesheet>>()
+        .get_mut(&handle)
+        .unwrap()
+        .vertical_spacing = 60.0;
+    app.update();
+    app.update();
+    assert_eq!(spacing(&app), 60.0);
+}
+
+#[test]
+fn handles_apply_to_the_resource_and_to_instances() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    let world = tester.app().world_mut();
+    let (main, pause) = {
+        let mut sheets = world.resource_mut::<Assets<Stylesheet>>();
+        (sheets.add(sheet(30.0)), sheets.add(sheet(50.0)))
+    };
+    world.insert_resource(StylesheetHandle(main.clone()));
+    let instance = world
+        .spawn((
+            NodeBundle::default(),
+            QuickMenu::new(BasicState::default(), Screens::Root, None),
+            StylesheetHandle(pause.clone()),
+        ))
+        .id();
+    let plain = world
+        .spawn((
+            NodeBundle::default(),
+            QuickMenu::new(BasicState::default(), Screens::Root, None),
+        ))
+        .id();
+    tester.advance(2);
+
+    let spacing = |tester: &mut MenuTester<Screens>, entity: Entity| {
+        tester
+            .app()
+            .world()
+            .get::<QuickMenu<Screens>>(entity)
+            .unwrap()
+            .menu_state()
+            .stylesheet()
+            .vertical_spacing
+    };
+    assert_eq!(tester.menu_state().stylesheet().vertical_spacing, 30.0);
+    assert_eq!(spacing(&mut tester, instance), 50.0);
+    assert_eq!(
+        spacing(&mut tester, plain),
+        Stylesheet::default().vertical_spacing
+    );
+
+    // Changes of an asset, e.g. on disk, are applied to its menus only
+    tester
+        .app()
+        .world_mut()
+        .resource_mut::<Assets<Stylesheet>>()
+        .get_mut(&pause)
+        .unwrap()
+        .vertical_spacing = 60.0;
+    tester.advance(2);
+    assert_eq!(spacing(&mut tester, instance), 60.0);
+    assert_eq!(
+        tester
+            .app()
+            .world()
+            .resource::<MenuState<Screens>>()
+            .stylesheet()
+            .vertical_spacing,
+        30.0
+    );
+}
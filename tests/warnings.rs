@@ -0,0 +1,47 @@
+mod common;
+
+use bevy_quickmenu::testing::MenuTester;
+use bevy_quickmenu::{MenuState, MenuWarning, NavigationEvent};
+use common::{BasicState, Screens};
+
+#[test]
+fn empty_screens_are_reported_once() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Empty);
+    assert_eq!(
+        tester.take_warnings(),
+        vec![MenuWarning::NoSelectableItems("empty".into())]
+    );
+
+    // Navigating, including repeated inputs, doesn't report it again
+    tester.send_all([NavigationEvent::Down; 5]);
+    tester.send(NavigationEvent::Select);
+    assert!(tester.take_warnings().is_empty());
+}
+
+#[test]
+fn pushed_empty_screens_are_reported_once_per_visit() {
+    let mut tester = MenuTester::new(BasicState::default(), Screens::Root);
+    assert!(tester.take_warnings().is_empty());
+
+    let push_empty = |tester: &mut MenuTester<Screens>| {
+        tester
+            .app()
+            .world_mut()
+            .resource_mut::<MenuState<Screens>>()
+            .push_screen(Screens::Empty);
+        tester.advance(2);
+    };
+    push_empty(&mut tester);
+    assert_eq!(
+        tester.take_warnings(),
+        vec![MenuWarning::NoSelectableItems("empty".into())]
+    );
+
+    tester.send(NavigationEvent::Back);
+    assert_eq!(tester.stack(), &[Screens::Root]);
+    push_empty(&mut tester);
+    assert_eq!(
+        tester.take_warnings(),
+        vec![MenuWarning::NoSelectableItems("empty".into())]
+    );
+}